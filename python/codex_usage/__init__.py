@@ -5,7 +5,7 @@ All functions return Python dicts/lists by parsing the JSON returned from Rust.
 """
 
 import json
-from typing import Optional, Dict, List, Any
+from typing import Optional, Dict, List, Any, Union
 
 
 def get_usage(
@@ -35,8 +35,7 @@ def get_usage(
         RuntimeError: If fetching usage fails
     """
     from codex_usage import get_usage as _get_usage
-    result = _get_usage(account, config_dir, refresh)
-    return json.loads(result)
+    return _get_usage(config_dir=config_dir, account=account, refresh=refresh)
 
 
 def list_accounts(config_dir: Optional[str] = None) -> List[Dict[str, Any]]:
@@ -53,8 +52,7 @@ def list_accounts(config_dir: Optional[str] = None) -> List[Dict[str, Any]]:
         - last_used: Optional[str]
     """
     from codex_usage import list_accounts as _list_accounts
-    result = _list_accounts(config_dir)
-    return json.loads(result)
+    return _list_accounts(config_dir=config_dir)
 
 
 def switch_account(
@@ -76,7 +74,7 @@ def switch_account(
         RuntimeError: If switching fails
     """
     from codex_usage import switch_account as _switch_account
-    return _switch_account(name, config_dir, force)
+    return _switch_account(name, force=force, config_dir=config_dir)
 
 
 def add_account(name: str, config_dir: Optional[str] = None) -> str:
@@ -124,7 +122,7 @@ def get_cycle_config(config_dir: Optional[str] = None) -> Dict[str, Any]:
         - enabled: bool
         - five_hour: float (threshold %)
         - weekly: float (threshold %)
-        - mode: str ("and" or "or")
+        - mode: str ("and" or "or"; "or" when no cycle.json exists yet)
         - accounts: List[str]
         - current_index: int
         - last_cycle: Optional[str]
@@ -224,6 +222,39 @@ def get_cycle_status(config_dir: Optional[str] = None) -> Dict[str, Any]:
     return json.loads(result)
 
 
+def query_history(
+    account: str,
+    from_: Union[str, int, float, None] = None,
+    to: Union[str, int, float, None] = None,
+    limit: Optional[int] = None,
+    config_dir: Optional[str] = None,
+) -> List[Dict[str, Any]]:
+    """Get recorded usage snapshots for an account, oldest first.
+
+    Args:
+        account: Account name
+        from_: Start of the range: "YYYY-MM-DD", an ISO 8601 timestamp or unix seconds
+        to: End of the range (a bare date covers the whole day)
+        limit: Keep only the most recent N snapshots (optional)
+        config_dir: Config directory path (optional)
+
+    Returns:
+        List of snapshot dictionaries with:
+        - account_name: str
+        - timestamp: int (unix seconds)
+        - five_hour_percent: Optional[float]
+        - weekly_percent: Optional[float]
+        - weekly_reset_timestamp: Optional[int]
+        - five_hour_reset_timestamp: Optional[int]
+        - plan: Optional[str]
+        - status: Optional[str]
+    """
+    from codex_usage import query_history as _query_history
+    return _query_history(
+        account, **{"from": from_}, to=to, limit=limit, config_dir=config_dir
+    )
+
+
 def get_config_dir() -> str:
     """Get the default config directory path.
 
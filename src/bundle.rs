@@ -0,0 +1,112 @@
+//! Passphrase encryption for exported account bundles.
+//!
+//! The key is derived with PBKDF2-HMAC-SHA256 and the payload sealed with
+//! AES-256-GCM; salt, nonce and ciphertext are stored base64-encoded in a
+//! small JSON envelope so the file stays self-describing.
+
+use anyhow::{Context, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroU32;
+
+const PBKDF2_ITERATIONS: u32 = 210_000;
+const SALT_LEN: usize = 16;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SealedBundle {
+    pub cipher: String,
+    pub kdf: String,
+    pub iterations: u32,
+    pub salt: String,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> Result<LessSafeKey> {
+    let iterations = NonZeroU32::new(iterations).context("Invalid PBKDF2 iteration count")?;
+    let mut key = [0u8; 32];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    let key = UnboundKey::new(&AES_256_GCM, &key)
+        .map_err(|_| anyhow::anyhow!("Failed to create encryption key"))?;
+    Ok(LessSafeKey::new(key))
+}
+
+pub fn seal(plaintext: &[u8], passphrase: &str) -> Result<SealedBundle> {
+    let rng = SystemRandom::new();
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rng.fill(&mut salt)
+        .and_then(|_| rng.fill(&mut nonce))
+        .map_err(|_| anyhow::anyhow!("Failed to generate random bytes"))?;
+
+    let key = derive_key(passphrase, &salt, PBKDF2_ITERATIONS)?;
+    let mut in_out = plaintext.to_vec();
+    key.seal_in_place_append_tag(
+        Nonce::assume_unique_for_key(nonce),
+        Aad::empty(),
+        &mut in_out,
+    )
+    .map_err(|_| anyhow::anyhow!("Failed to encrypt bundle"))?;
+
+    Ok(SealedBundle {
+        cipher: "aes-256-gcm".to_string(),
+        kdf: "pbkdf2-sha256".to_string(),
+        iterations: PBKDF2_ITERATIONS,
+        salt: STANDARD.encode(salt),
+        nonce: STANDARD.encode(nonce),
+        ciphertext: STANDARD.encode(in_out),
+    })
+}
+
+pub fn open(sealed: &SealedBundle, passphrase: &str) -> Result<Vec<u8>> {
+    if sealed.cipher != "aes-256-gcm" || sealed.kdf != "pbkdf2-sha256" {
+        anyhow::bail!(
+            "Unsupported bundle encryption ({}/{})",
+            sealed.cipher,
+            sealed.kdf
+        );
+    }
+    let salt = STANDARD
+        .decode(&sealed.salt)
+        .context("Invalid bundle salt")?;
+    let nonce: [u8; NONCE_LEN] = STANDARD
+        .decode(&sealed.nonce)
+        .ok()
+        .and_then(|n| n.try_into().ok())
+        .context("Invalid bundle nonce")?;
+    let mut in_out = STANDARD
+        .decode(&sealed.ciphertext)
+        .context("Invalid bundle ciphertext")?;
+
+    let key = derive_key(passphrase, &salt, sealed.iterations)?;
+    let plaintext = key
+        .open_in_place(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::empty(),
+            &mut in_out,
+        )
+        .map_err(|_| anyhow::anyhow!("Failed to decrypt bundle (wrong passphrase?)"))?;
+    Ok(plaintext.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_open_roundtrip() {
+        let sealed = seal(b"{\"tokens\":{}}", "hunter2").expect("seal");
+        assert_eq!(open(&sealed, "hunter2").expect("open"), b"{\"tokens\":{}}");
+        assert!(open(&sealed, "wrong").is_err());
+    }
+}
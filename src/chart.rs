@@ -0,0 +1,249 @@
+//! Interactive terminal chart of recorded usage history.
+
+use crate::history::UsageSnapshot;
+use anyhow::Result;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use ratatui::crossterm::ExecutableCommand;
+use ratatui::prelude::*;
+use ratatui::widgets::{Axis, Block, Chart, Dataset, GraphType, Paragraph};
+use std::io::stdout;
+use std::time::Duration;
+
+/// Visible time spans in seconds, narrowest first; `None` shows all history.
+const ZOOM_LEVELS: [Option<i64>; 5] = [
+    Some(6 * 3600),
+    Some(24 * 3600),
+    Some(7 * 24 * 3600),
+    Some(30 * 24 * 3600),
+    None,
+];
+
+/// Snapshots for one account, oldest first.
+pub struct AccountSeries {
+    pub account: String,
+    pub snapshots: Vec<UsageSnapshot>,
+}
+
+struct ChartState {
+    series: Vec<AccountSeries>,
+    selected: usize,
+    zoom: usize,
+    /// Right edge of the view when panned back; `None` follows the latest snapshot.
+    end: Option<i64>,
+}
+
+impl ChartState {
+    fn current(&self) -> &AccountSeries {
+        &self.series[self.selected]
+    }
+
+    fn bounds(&self) -> (i64, i64) {
+        let snapshots = &self.current().snapshots;
+        let first = snapshots.first().map(|s| s.timestamp).unwrap_or(0);
+        let last = snapshots.last().map(|s| s.timestamp).unwrap_or(0);
+        (first, last)
+    }
+
+    fn range(&self) -> (i64, i64) {
+        let (first, last) = self.bounds();
+        visible_range(first, last, ZOOM_LEVELS[self.zoom], self.end)
+    }
+
+    fn pan(&mut self, forward: bool) {
+        let Some(span) = ZOOM_LEVELS[self.zoom] else {
+            return;
+        };
+        let (first, last) = self.bounds();
+        let (_, end) = self.range();
+        let end = if forward {
+            end + span / 2
+        } else {
+            end - span / 2
+        };
+        self.end = (end < last).then(|| end.max(first + span));
+    }
+
+    fn select(&mut self, delta: isize) {
+        let len = self.series.len() as isize;
+        self.selected = (self.selected as isize + delta).rem_euclid(len) as usize;
+        self.end = None;
+    }
+
+    fn points(&self, value: impl Fn(&UsageSnapshot) -> Option<f64>) -> Vec<(f64, f64)> {
+        let (start, end) = self.range();
+        self.current()
+            .snapshots
+            .iter()
+            .filter(|s| s.timestamp >= start && s.timestamp <= end)
+            .filter_map(|s| Some((s.timestamp as f64, value(s)?.clamp(0.0, 100.0))))
+            .collect()
+    }
+}
+
+/// Time range to display: `span` seconds ending at `end` (or the latest
+/// point), never starting before the first point.
+fn visible_range(first: i64, last: i64, span: Option<i64>, end: Option<i64>) -> (i64, i64) {
+    let end = end.unwrap_or(last).min(last);
+    match span {
+        Some(span) => ((end - span).max(first), end.max(first + 1)),
+        None => (first, last.max(first + 1)),
+    }
+}
+
+fn zoom_label(zoom: Option<i64>) -> String {
+    match zoom {
+        Some(secs) if secs >= 24 * 3600 => format!("{}d", secs / (24 * 3600)),
+        Some(secs) => format!("{}h", secs / 3600),
+        None => "all".to_string(),
+    }
+}
+
+fn time_label(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|d| {
+            d.with_timezone(&chrono::Local)
+                .format("%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default()
+}
+
+fn render(frame: &mut Frame, state: &ChartState) {
+    let [chart_area, help_area] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+
+    let five_hour = state.points(|s| s.five_hour_percent);
+    let weekly = state.points(|s| s.weekly_percent);
+    let datasets = vec![
+        Dataset::default()
+            .name("5h")
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Cyan))
+            .data(&five_hour),
+        Dataset::default()
+            .name("weekly")
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Yellow))
+            .data(&weekly),
+    ];
+
+    let (start, end) = state.range();
+    let title = format!(
+        " {} ({}/{}) - last {} ",
+        state.current().account,
+        state.selected + 1,
+        state.series.len(),
+        zoom_label(ZOOM_LEVELS[state.zoom])
+    );
+    let chart = Chart::new(datasets)
+        .block(Block::bordered().title(title))
+        .x_axis(Axis::default().bounds([start as f64, end as f64]).labels([
+            time_label(start),
+            time_label(start + (end - start) / 2),
+            time_label(end),
+        ]))
+        .y_axis(
+            Axis::default()
+                .title("used %")
+                .bounds([0.0, 100.0])
+                .labels(["0", "50", "100"]),
+        );
+    frame.render_widget(chart, chart_area);
+
+    let help =
+        Paragraph::new("q quit | tab/n p switch account | +/- zoom | \u{2190}/\u{2192} scroll")
+            .style(Style::default().add_modifier(Modifier::DIM));
+    frame.render_widget(help, help_area);
+}
+
+fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = stdout().execute(LeaveAlternateScreen);
+}
+
+/// Runs the interactive chart until `q`, `Esc` or Ctrl-C, restoring the
+/// terminal on exit (including on panic).
+pub fn run(series: Vec<AccountSeries>) -> Result<()> {
+    if series.is_empty() {
+        anyhow::bail!("No history to chart.");
+    }
+    let mut state = ChartState {
+        series,
+        selected: 0,
+        zoom: 1,
+        end: None,
+    };
+
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        restore_terminal();
+        default_hook(info);
+    }));
+
+    enable_raw_mode()?;
+    stdout().execute(EnterAlternateScreen)?;
+    let result = event_loop(&mut state);
+    restore_terminal();
+    let _ = std::panic::take_hook();
+    result
+}
+
+fn event_loop(state: &mut ChartState) -> Result<()> {
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
+    loop {
+        terminal.draw(|frame| render(frame, state))?;
+        if !event::poll(Duration::from_millis(250))? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return Ok(()),
+            KeyCode::Tab | KeyCode::Char('n') | KeyCode::Down => state.select(1),
+            KeyCode::BackTab | KeyCode::Char('p') | KeyCode::Up => state.select(-1),
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                state.zoom = state.zoom.saturating_sub(1);
+                state.end = None;
+            }
+            KeyCode::Char('-') => {
+                state.zoom = (state.zoom + 1).min(ZOOM_LEVELS.len() - 1);
+                state.end = None;
+            }
+            KeyCode::Left => state.pan(false),
+            KeyCode::Right => state.pan(true),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visible_range_clamps_to_history() {
+        assert_eq!(
+            visible_range(0, 100_000, Some(3600), None),
+            (96_400, 100_000)
+        );
+        assert_eq!(
+            visible_range(99_000, 100_000, Some(3600), None),
+            (99_000, 100_000)
+        );
+        assert_eq!(
+            visible_range(0, 100_000, Some(3600), Some(50_000)),
+            (46_400, 50_000)
+        );
+        assert_eq!(visible_range(10, 10, None, None), (10, 11));
+    }
+}
@@ -38,6 +38,13 @@ impl HistoryDatabase {
     pub fn new(config_dir: &Path) -> Result<Self> {
         let db_path = config_dir.join("history.db");
         let conn = Connection::open(&db_path).context("Failed to open history database")?;
+        // WAL lets readers (show, chart) run while the daemon writes, and the
+        // busy timeout makes competing writers wait instead of failing with
+        // "database is locked".
+        conn.busy_timeout(std::time::Duration::from_millis(5000))
+            .context("Failed to set history database busy timeout")?;
+        conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get::<_, String>(0))
+            .context("Failed to enable WAL on history database")?;
 
         conn.execute(
             "CREATE TABLE IF NOT EXISTS usage_snapshots (
@@ -98,6 +105,37 @@ impl HistoryDatabase {
         Ok(conn.last_insert_rowid())
     }
 
+    /// Inserts all `snapshots` in one transaction, so a daemon pass over many
+    /// accounts costs a single commit rather than one per row. Returns the
+    /// number of rows inserted.
+    pub fn insert_snapshots(&self, snapshots: &[UsageSnapshot]) -> Result<usize> {
+        let mut conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("lock poisoned: {}", e))?;
+        let tx = conn.transaction()?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO usage_snapshots (account_name, timestamp, five_hour_percent, weekly_percent, weekly_reset_timestamp, five_hour_reset_timestamp, plan, status)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            )?;
+            for snapshot in snapshots {
+                stmt.execute(params![
+                    snapshot.account_name,
+                    snapshot.timestamp,
+                    snapshot.five_hour_percent,
+                    snapshot.weekly_percent,
+                    snapshot.weekly_reset_timestamp,
+                    snapshot.five_hour_reset_timestamp,
+                    snapshot.plan,
+                    snapshot.status,
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(snapshots.len())
+    }
+
     pub fn get_snapshots(
         &self,
         account_name: &str,
@@ -109,100 +147,42 @@ impl HistoryDatabase {
             .conn
             .lock()
             .map_err(|e| anyhow::anyhow!("lock poisoned: {}", e))?;
-        let mut sql = String::from("SELECT id, account_name, timestamp, five_hour_percent, weekly_percent, weekly_reset_timestamp, five_hour_reset_timestamp, plan, status FROM usage_snapshots WHERE account_name = ?1");
-
-        let from_param = from_timestamp.as_ref();
-        let to_param = to_timestamp.as_ref();
-
-        if from_param.is_some() {
-            sql.push_str(" AND timestamp >= ?2");
-        }
-        if to_param.is_some() {
-            sql.push_str(" AND timestamp <= ?3");
-        }
-        sql.push_str(" ORDER BY timestamp DESC");
-
-        if let Some(l) = limit {
-            sql.push_str(&format!(" LIMIT {}", l));
-        }
+        // NULL bounds match everything and a negative LIMIT means no limit,
+        // so one statement covers every combination of filters.
+        let mut stmt = conn.prepare(
+            "SELECT id, account_name, timestamp, five_hour_percent, weekly_percent, weekly_reset_timestamp, five_hour_reset_timestamp, plan, status FROM usage_snapshots
+             WHERE account_name = ?1
+               AND (?2 IS NULL OR timestamp >= ?2)
+               AND (?3 IS NULL OR timestamp <= ?3)
+             ORDER BY timestamp DESC
+             LIMIT ?4",
+        )?;
 
-        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(
+            params![
+                account_name,
+                from_timestamp,
+                to_timestamp,
+                limit.unwrap_or(-1)
+            ],
+            |row| {
+                Ok(UsageSnapshot {
+                    id: Some(row.get(0)?),
+                    account_name: row.get(1)?,
+                    timestamp: row.get(2)?,
+                    five_hour_percent: row.get(3)?,
+                    weekly_percent: row.get(4)?,
+                    weekly_reset_timestamp: row.get(5)?,
+                    five_hour_reset_timestamp: row.get(6)?,
+                    plan: row.get(7)?,
+                    status: row.get(8)?,
+                })
+            },
+        )?;
 
         let mut snapshots = Vec::new();
-
-        match (from_param, to_param) {
-            (Some(from), Some(to)) => {
-                let rows = stmt.query_map(params![account_name, from, to], |row| {
-                    Ok(UsageSnapshot {
-                        id: Some(row.get(0)?),
-                        account_name: row.get(1)?,
-                        timestamp: row.get(2)?,
-                        five_hour_percent: row.get(3)?,
-                        weekly_percent: row.get(4)?,
-                        weekly_reset_timestamp: row.get(5)?,
-                        five_hour_reset_timestamp: row.get(6)?,
-                        plan: row.get(7)?,
-                        status: row.get(8)?,
-                    })
-                })?;
-                for row in rows {
-                    snapshots.push(row?);
-                }
-            }
-            (Some(from), None) => {
-                let rows = stmt.query_map(params![account_name, from], |row| {
-                    Ok(UsageSnapshot {
-                        id: Some(row.get(0)?),
-                        account_name: row.get(1)?,
-                        timestamp: row.get(2)?,
-                        five_hour_percent: row.get(3)?,
-                        weekly_percent: row.get(4)?,
-                        weekly_reset_timestamp: row.get(5)?,
-                        five_hour_reset_timestamp: row.get(6)?,
-                        plan: row.get(7)?,
-                        status: row.get(8)?,
-                    })
-                })?;
-                for row in rows {
-                    snapshots.push(row?);
-                }
-            }
-            (None, Some(_to)) => {
-                let rows = stmt.query_map(params![account_name], |row| {
-                    Ok(UsageSnapshot {
-                        id: Some(row.get(0)?),
-                        account_name: row.get(1)?,
-                        timestamp: row.get(2)?,
-                        five_hour_percent: row.get(3)?,
-                        weekly_percent: row.get(4)?,
-                        weekly_reset_timestamp: row.get(5)?,
-                        five_hour_reset_timestamp: row.get(6)?,
-                        plan: row.get(7)?,
-                        status: row.get(8)?,
-                    })
-                })?;
-                for row in rows {
-                    snapshots.push(row?);
-                }
-            }
-            (None, None) => {
-                let rows = stmt.query_map(params![account_name], |row| {
-                    Ok(UsageSnapshot {
-                        id: Some(row.get(0)?),
-                        account_name: row.get(1)?,
-                        timestamp: row.get(2)?,
-                        five_hour_percent: row.get(3)?,
-                        weekly_percent: row.get(4)?,
-                        weekly_reset_timestamp: row.get(5)?,
-                        five_hour_reset_timestamp: row.get(6)?,
-                        plan: row.get(7)?,
-                        status: row.get(8)?,
-                    })
-                })?;
-                for row in rows {
-                    snapshots.push(row?);
-                }
-            }
+        for row in rows {
+            snapshots.push(row?);
         }
 
         Ok(snapshots)
@@ -252,7 +232,6 @@ impl HistoryDatabase {
         Ok(())
     }
 
-    #[allow(dead_code)]
     pub fn update_last_notified(&self, account_name: &str) -> Result<()> {
         let conn = self
             .conn
@@ -266,7 +245,6 @@ impl HistoryDatabase {
         Ok(())
     }
 
-    #[allow(dead_code)]
     pub fn get_all_notification_configs(&self) -> Result<Vec<NotificationConfig>> {
         let conn = self
             .conn
@@ -293,6 +271,48 @@ impl HistoryDatabase {
         Ok(configs)
     }
 
+    /// Timestamp of the most recent snapshot for any account.
+    pub fn last_snapshot_timestamp(&self) -> Result<Option<i64>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("lock poisoned: {}", e))?;
+        let ts = conn.query_row("SELECT MAX(timestamp) FROM usage_snapshots", [], |row| {
+            row.get(0)
+        })?;
+        Ok(ts)
+    }
+
+    /// Moves all snapshots and notification settings from `from` to `to`.
+    pub fn rename_account(&self, from: &str, to: &str) -> Result<usize> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("lock poisoned: {}", e))?;
+        let moved = conn.execute(
+            "UPDATE usage_snapshots SET account_name = ?2 WHERE account_name = ?1",
+            params![from, to],
+        )?;
+        conn.execute(
+            "UPDATE OR REPLACE notification_config SET account_name = ?2 WHERE account_name = ?1",
+            params![from, to],
+        )?;
+        Ok(moved)
+    }
+
+    /// Deletes snapshots recorded before `cutoff` and returns how many were removed.
+    pub fn prune_older_than(&self, cutoff: i64) -> Result<usize> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("lock poisoned: {}", e))?;
+        let deleted = conn.execute(
+            "DELETE FROM usage_snapshots WHERE timestamp < ?1",
+            params![cutoff],
+        )?;
+        Ok(deleted)
+    }
+
     pub fn get_accounts(&self) -> Result<Vec<String>> {
         let conn = self
             .conn
@@ -314,3 +334,74 @@ impl HistoryDatabase {
 pub fn get_history_db_path(config_dir: &Path) -> std::path::PathBuf {
     config_dir.join("history.db")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(account_name: &str, timestamp: i64) -> UsageSnapshot {
+        UsageSnapshot {
+            id: None,
+            account_name: account_name.to_string(),
+            timestamp,
+            five_hour_percent: Some(10.0),
+            weekly_percent: Some(20.0),
+            weekly_reset_timestamp: None,
+            five_hour_reset_timestamp: None,
+            plan: None,
+            status: None,
+        }
+    }
+
+    #[test]
+    fn test_two_connections_share_the_database() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let writer = HistoryDatabase::new(tmp.path()).expect("open writer");
+        let reader = HistoryDatabase::new(tmp.path()).expect("open reader");
+
+        let journal_mode: String = reader
+            .conn
+            .lock()
+            .expect("lock")
+            .query_row("PRAGMA journal_mode", [], |row| row.get(0))
+            .expect("journal mode");
+        assert_eq!(journal_mode.to_lowercase(), "wal");
+
+        let handle = std::thread::spawn(move || {
+            for i in 0..50 {
+                writer
+                    .insert_snapshot(&snapshot("work", i))
+                    .expect("insert while another connection reads");
+            }
+        });
+        for _ in 0..50 {
+            reader
+                .get_snapshots("work", None, None, None)
+                .expect("read while another connection writes");
+        }
+        handle.join().expect("writer thread");
+        assert_eq!(
+            reader
+                .get_snapshots("work", None, None, None)
+                .expect("read")
+                .len(),
+            50
+        );
+    }
+
+    #[test]
+    fn test_insert_snapshots_batches_rows() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let db = HistoryDatabase::new(tmp.path()).expect("open");
+
+        let batch: Vec<UsageSnapshot> = (0..3).map(|i| snapshot("work", i)).collect();
+        assert_eq!(db.insert_snapshots(&batch).expect("insert batch"), 3);
+        assert_eq!(db.insert_snapshots(&[]).expect("insert empty"), 0);
+        assert_eq!(
+            db.get_snapshots("work", None, None, None)
+                .expect("read")
+                .len(),
+            3
+        );
+    }
+}
@@ -7,6 +7,11 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 pub mod history;
+pub mod logging;
+pub mod model;
+mod paths;
+
+pub use model::{AccountInfo, Config, CycleConfig, CycleThresholds};
 
 #[cfg(unix)]
 use std::process::Command;
@@ -33,6 +38,7 @@ fn codex_usage(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(cycle_now, m)?)?;
     m.add_function(wrap_pyfunction!(get_cycle_status, m)?)?;
     m.add_function(wrap_pyfunction!(get_config_dir, m)?)?;
+    m.add_function(wrap_pyfunction!(query_history, m)?)?;
     Ok(())
 }
 
@@ -91,12 +97,57 @@ mod tests {
         let resolved = get_account_auth_path(&config_dir, account_name).unwrap();
         assert_eq!(resolved, expected);
     }
+
+    #[test]
+    fn history_bound_accepts_dates_rfc3339_and_unix_seconds() {
+        assert_eq!(
+            parse_history_bound("2025-01-01", false).unwrap(),
+            1735689600
+        );
+        assert_eq!(
+            parse_history_bound("2025-01-01", true).unwrap(),
+            1735689600 + 86_399
+        );
+        assert_eq!(
+            parse_history_bound("2025-01-01T01:00:00+01:00", false).unwrap(),
+            1735689600
+        );
+        assert_eq!(
+            parse_history_bound("1700000000", true).unwrap(),
+            1_700_000_000
+        );
+        assert!(parse_history_bound("yesterday", false).is_err());
+    }
+
+    #[test]
+    fn cycle_defaults_to_or_mode_without_a_cycle_json() {
+        // The bindings defaulted to "and" before sharing the CLI's model.
+        let tmp = TempDir::new().unwrap();
+        let config = load_cycle_config(tmp.path()).unwrap();
+        assert_eq!(config.mode, "or");
+
+        let window = |remaining_percent: f64| RateWindow {
+            used_percent: 100.0 - remaining_percent,
+            remaining_percent,
+            window: "5h".to_string(),
+            resets_in: None,
+        };
+        let usage = UsageData {
+            account_name: "work".to_string(),
+            status: "ok".to_string(),
+            plan: None,
+            primary_window: Some(window(0.0)),
+            secondary_window: Some(window(50.0)),
+            code_review: None,
+            limit_reached: false,
+            auth_type: "oauth".to_string(),
+        };
+        assert!(should_cycle(&usage, &config).0);
+    }
 }
 
 fn get_config_dir_default() -> PathBuf {
-    dirs::home_dir()
-        .map(|p| p.join(".codex-usage"))
-        .unwrap_or_else(|| PathBuf::from(".codex-usage"))
+    paths::default_config_dir()
 }
 
 #[cfg(feature = "pyo3")]
@@ -105,21 +156,43 @@ fn get_config_dir() -> String {
     get_config_dir_default().to_string_lossy().to_string()
 }
 
+/// Converts a serializable value into the equivalent Python object
+/// (dict/list/str/...) by round-tripping through the stdlib `json` module.
+#[cfg(feature = "pyo3")]
+fn to_py_object<T: Serialize>(py: Python<'_>, value: &T) -> PyResult<PyObject> {
+    let json = serde_json::to_string(value)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    Ok(py.import("json")?.call_method1("loads", (json,))?.into())
+}
+
+/// Return usage for `account` (default: the active account) as a dict with
+/// the same fields as `codex-usage status --format json --bare`.
+///
+/// A cached result younger than five minutes is returned unless `refresh`
+/// is true; fresh results are written back to the cache. Raises
+/// RuntimeError if the account has no usable auth or the API call fails.
 #[cfg(feature = "pyo3")]
 #[pyfunction]
+#[pyo3(signature = (config_dir=None, account=None, refresh=false))]
 fn get_usage(
-    account: Option<String>,
+    py: Python<'_>,
     config_dir: Option<String>,
-    refresh: Option<bool>,
-) -> PyResult<String> {
-    let refresh = refresh.unwrap_or(false);
+    account: Option<String>,
+    refresh: bool,
+) -> PyResult<PyObject> {
     let config_dir = config_dir
         .map(PathBuf::from)
         .unwrap_or_else(get_config_dir_default);
-
-    let config = load_config(&config_dir)
+    let usage = load_usage(&config_dir, account, refresh)
         .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    to_py_object(py, &usage)
+}
 
+/// Usage for `account` (or the active account) from the cache when fresh,
+/// otherwise from the API.
+#[cfg(feature = "pyo3")]
+fn load_usage(config_dir: &Path, account: Option<String>, refresh: bool) -> Result<UsageData> {
+    let config = load_config(config_dir)?;
     let account_name = account.unwrap_or_else(|| {
         config
             .active_account
@@ -127,103 +200,36 @@ fn get_usage(
             .unwrap_or_else(|| "default".to_string())
     });
 
-    let codex_auth_path = if config.accounts.contains_key(&account_name) {
-        get_account_auth_path(&config_dir, &account_name)
-            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?
-    } else {
-        get_codex_auth_path()
-    };
-
-    let auth = load_codex_auth(&codex_auth_path)
-        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
-
-    let tokens = match auth {
-        Some(a) => a.tokens,
-        None => {
-            return Err(pyo3::exceptions::PyRuntimeError::new_err(
-                "No auth found. Run 'codex login' first.",
-            ))
-        }
-    };
-
-    let tokens = match tokens {
-        Some(t) => t,
-        None => {
-            return Err(pyo3::exceptions::PyRuntimeError::new_err(
-                "No tokens found in auth",
-            ))
-        }
-    };
-
-    let access_token = match tokens.access_token {
-        Some(at) => at,
-        None => {
-            return Err(pyo3::exceptions::PyRuntimeError::new_err(
-                "Missing access_token",
-            ))
-        }
-    };
-
-    let account_id = match tokens.account_id {
-        Some(ai) => ai,
-        None => {
-            return Err(pyo3::exceptions::PyRuntimeError::new_err(
-                "Missing account_id",
-            ))
+    if !refresh {
+        if let Some(usage) = get_cached_usage(config_dir, &account_name) {
+            return Ok(usage);
         }
-    };
+    }
 
-    let usage = if !refresh {
-        get_cached_usage(&config_dir, &account_name)
+    let codex_auth_path = if config.accounts.contains_key(&account_name) {
+        get_account_auth_path(config_dir, &account_name)?
     } else {
-        None
-    };
-
-    let usage = match usage {
-        Some(u) => u,
-        None => {
-            let client = reqwest::blocking::Client::new();
-            let response = client
-                .get(USAGE_API_URL)
-                .header("Authorization", format!("Bearer {}", access_token))
-                .header("chatgpt-account-id", account_id)
-                .header("User-Agent", "codex-cli")
-                .header("Content-Type", "application/json")
-                .timeout(std::time::Duration::from_secs(10))
-                .send()
-                .map_err(|e| {
-                    pyo3::exceptions::PyRuntimeError::new_err(format!(
-                        "Failed to fetch usage: {}",
-                        e
-                    ))
-                })?;
-
-            if !response.status().is_success() {
-                return Err(pyo3::exceptions::PyRuntimeError::new_err(format!(
-                    "API returned error: {}",
-                    response.status()
-                )));
-            }
-
-            let data: serde_json::Value = response.json().map_err(|e| {
-                pyo3::exceptions::PyRuntimeError::new_err(format!(
-                    "Failed to parse response: {}",
-                    e
-                ))
-            })?;
-            let mut usage = parse_usage_response(data, &account_name);
-            let _ = save_cache(&config_dir, &usage, &account_name);
-            usage
-        }
+        get_codex_auth_path()
     };
+    let tokens = load_codex_auth(&codex_auth_path)?
+        .context("No auth found. Run 'codex login' first.")?
+        .tokens
+        .context("No tokens found in auth")?;
+    let access_token = tokens.access_token.context("Missing access_token")?;
+    let account_id = tokens.account_id.context("Missing account_id")?;
 
-    serde_json::to_string(&usage)
-        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+    let client = reqwest::blocking::Client::new();
+    let usage = fetch_usage(&client, &access_token, &account_id, &account_name)?;
+    let _ = save_cache(config_dir, &usage, &account_name);
+    Ok(usage)
 }
 
+/// Return the configured accounts as a list of dicts with `name`,
+/// `added_at`, `last_used` and `active` keys.
 #[cfg(feature = "pyo3")]
 #[pyfunction]
-fn list_accounts(config_dir: Option<String>) -> PyResult<String> {
+#[pyo3(signature = (config_dir=None))]
+fn list_accounts(py: Python<'_>, config_dir: Option<String>) -> PyResult<PyObject> {
     let config_dir = config_dir
         .map(PathBuf::from)
         .unwrap_or_else(get_config_dir_default);
@@ -237,25 +243,24 @@ fn list_accounts(config_dir: Option<String>) -> PyResult<String> {
         .map(|(name, info)| {
             serde_json::json!({
                 "name": name,
-                "active": config.active_account.as_deref() == Some(name),
                 "added_at": info.added_at,
-                "last_used": info.last_used
+                "last_used": info.last_used,
+                "active": config.active_account.as_deref() == Some(name),
             })
         })
         .collect();
 
-    serde_json::to_string(&accounts)
-        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+    to_py_object(py, &accounts)
 }
 
+/// Make `name` the active account by copying its auth into ~/.codex.
+///
+/// Refuses while Codex is running unless `force` is true. Raises
+/// RuntimeError if the account doesn't exist or the switch fails.
 #[cfg(feature = "pyo3")]
 #[pyfunction]
-fn switch_account(
-    name: String,
-    config_dir: Option<String>,
-    force: Option<bool>,
-) -> PyResult<String> {
-    let force = force.unwrap_or(false);
+#[pyo3(signature = (name, force=false, config_dir=None))]
+fn switch_account(name: String, force: bool, config_dir: Option<String>) -> PyResult<String> {
     let config_dir = config_dir
         .map(PathBuf::from)
         .unwrap_or_else(get_config_dir_default);
@@ -412,6 +417,82 @@ fn get_cycle_status(config_dir: Option<String>) -> PyResult<String> {
         .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
 }
 
+/// Parses a history bound given as `YYYY-MM-DD` (UTC; `end_of_day` selects
+/// its last second), an RFC 3339 timestamp or unix seconds into unix seconds.
+#[cfg(any(feature = "pyo3", test))]
+fn parse_history_bound(value: &str, end_of_day: bool) -> Result<i64> {
+    if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) {
+        return value
+            .parse()
+            .with_context(|| format!("Invalid date '{}': unix seconds out of range", value));
+    }
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        let time = if end_of_day {
+            chrono::NaiveTime::from_hms_opt(23, 59, 59)
+        } else {
+            Some(chrono::NaiveTime::MIN)
+        };
+        return Ok(date
+            .and_time(time.unwrap_or_default())
+            .and_utc()
+            .timestamp());
+    }
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.timestamp())
+        .with_context(|| {
+            format!(
+                "Invalid date '{}': expected YYYY-MM-DD, an RFC 3339 timestamp or unix seconds",
+                value
+            )
+        })
+}
+
+#[cfg(feature = "pyo3")]
+fn history_bound(value: Option<&PyAny>, end_of_day: bool) -> PyResult<Option<i64>> {
+    let Some(value) = value.filter(|v| !v.is_none()) else {
+        return Ok(None);
+    };
+    if let Ok(text) = value.extract::<&str>() {
+        return parse_history_bound(text, end_of_day)
+            .map(Some)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()));
+    }
+    if let Ok(secs) = value.extract::<i64>() {
+        return Ok(Some(secs));
+    }
+    Ok(Some(value.extract::<f64>()? as i64))
+}
+
+/// Return recorded usage snapshots for `account` as a list of dicts, oldest
+/// first, ready for `pandas.DataFrame`.
+///
+/// `from` and `to` bound the range and accept `YYYY-MM-DD` (UTC; `to` covers
+/// the whole day), RFC 3339 strings or unix timestamps. `limit` keeps only
+/// the most recent snapshots.
+#[cfg(feature = "pyo3")]
+#[pyfunction]
+#[pyo3(signature = (account, r#from=None, to=None, limit=None, config_dir=None))]
+fn query_history(
+    py: Python<'_>,
+    account: String,
+    r#from: Option<&PyAny>,
+    to: Option<&PyAny>,
+    limit: Option<i64>,
+    config_dir: Option<String>,
+) -> PyResult<PyObject> {
+    let config_dir = config_dir
+        .map(PathBuf::from)
+        .unwrap_or_else(get_config_dir_default);
+    let from_ts = history_bound(r#from, false)?;
+    let to_ts = history_bound(to, true)?;
+
+    let mut snapshots = history::HistoryDatabase::new(&config_dir)
+        .and_then(|db| db.get_snapshots(&account, from_ts, to_ts, limit))
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    snapshots.reverse();
+    to_py_object(py, &snapshots)
+}
+
 #[cfg(feature = "pyo3")]
 #[pyfunction]
 fn run_py(py: Python<'_>) -> PyResult<String> {
@@ -745,13 +826,23 @@ pub struct Cli {
     #[command(subcommand)]
     command: Commands,
 
-    /// Path to config directory (default: ~/.codex-usage)
+    /// Path to config directory (default: $XDG_CONFIG_HOME/codex-usage, or an existing ~/.codex-usage)
     #[arg(short, long, env = "CODEX_USAGE_DIR")]
     pub config_dir: Option<PathBuf>,
 
     /// Enable verbose logging
     #[arg(short, long, global = true, env = "CODEX_USAGE_VERBOSE")]
     pub verbose: bool,
+
+    /// Log line format
+    #[arg(
+        long,
+        global = true,
+        env = "CODEX_USAGE_LOG_FORMAT",
+        value_enum,
+        default_value = "text"
+    )]
+    pub log_format: logging::LogFormat,
 }
 
 #[derive(Subcommand)]
@@ -1015,48 +1106,6 @@ pub enum DaemonCommands {
     Status,
 }
 
-#[derive(Debug, Serialize, Deserialize, Default)]
-pub struct Config {
-    pub active_account: Option<String>,
-    pub accounts: HashMap<String, AccountInfo>,
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct AccountInfo {
-    pub added_at: String,
-    pub last_used: Option<String>,
-    pub auth_hash: Option<String>,
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct CycleConfig {
-    pub enabled: bool,
-    pub thresholds: CycleThresholds,
-    pub mode: String,
-    pub accounts: Vec<String>,
-    pub current_index: usize,
-    pub last_cycle: Option<String>,
-}
-
-impl Default for CycleConfig {
-    fn default() -> Self {
-        Self {
-            enabled: false,
-            thresholds: CycleThresholds::default(),
-            mode: "and".to_string(),
-            accounts: Vec::new(),
-            current_index: 0,
-            last_cycle: None,
-        }
-    }
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
-pub struct CycleThresholds {
-    pub five_hour: f64,
-    pub weekly: f64,
-}
-
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CycleHistoryEntry {
     pub timestamp: String,
@@ -1178,7 +1227,15 @@ pub fn load_config(config_dir: &Path) -> Result<Config> {
     let config_path = get_config_path(config_dir);
     if config_path.exists() {
         let content = fs::read_to_string(&config_path)?;
-        let config: Config = serde_json::from_str(&content).context("Failed to parse config")?;
+        let mut value: serde_json::Value =
+            serde_json::from_str(&content).context("Failed to parse config")?;
+        let migrated = model::migrate_config(&mut value)?;
+        let config: Config = serde_json::from_value(value).context("Failed to parse config")?;
+        if migrated {
+            if let Err(e) = save_config(config_dir, &config) {
+                eprintln!("Warning: Failed to save migrated config: {}", e);
+            }
+        }
         Ok(config)
     } else {
         Ok(Config::default())
@@ -1256,13 +1313,13 @@ fn is_codex_running() -> bool {
                 }
                 #[cfg(windows)]
                 {
+                    // The lock is held for as long as the process lives, so an
+                    // idle (sleeping) Codex still counts; only a dead PID means
+                    // the lock file is stale.
                     use sysinfo::System;
                     let mut sys = System::new();
                     sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
-                    if let Some(process) = sys.process(sysinfo::Pid::from_u32(pid)) {
-                        return process.status() != sysinfo::ProcessStatus::Run;
-                    }
-                    return false;
+                    return sys.process(sysinfo::Pid::from_u32(pid)).is_some();
                 }
             }
         }
@@ -1355,6 +1412,8 @@ pub fn cmd_accounts_add(config_dir: &Path, name: &str) -> Result<()> {
             added_at: chrono::Utc::now().to_rfc3339(),
             last_used: None,
             auth_hash: Some(auth_hash),
+            aliases: Vec::new(),
+            email: None,
         },
     );
     save_config(config_dir, &config)?;
@@ -1952,40 +2011,13 @@ fn should_cycle(usage: &UsageData, config: &CycleConfig) -> (bool, String) {
         .map(|w| w.remaining_percent)
         .unwrap_or(100.0);
 
-    let five_hour_trigger = five_hour_remaining <= config.thresholds.five_hour;
-    let weekly_trigger = weekly_remaining <= config.thresholds.weekly;
-
-    let reason = if config.mode == "and" {
-        if five_hour_trigger && weekly_trigger {
-            let parts = [
-                format!("5h: {:.0}% remaining", five_hour_remaining),
-                format!("weekly: {:.0}% remaining", weekly_remaining),
-            ];
-            (true, parts.join(", "))
-        } else {
-            (
-                false,
-                format!(
-                    "5h: {:.0}%, weekly: {:.0}%",
-                    five_hour_remaining, weekly_remaining
-                ),
-            )
-        }
-    } else if five_hour_trigger {
-        (true, format!("5h: {:.0}% remaining", five_hour_remaining))
-    } else if weekly_trigger {
-        (true, format!("weekly: {:.0}% remaining", weekly_remaining))
-    } else {
-        (
-            false,
-            format!(
-                "5h: {:.0}%, weekly: {:.0}%",
-                five_hour_remaining, weekly_remaining
-            ),
-        )
-    };
+    let code_review_remaining = usage
+        .code_review
+        .as_ref()
+        .map(|c| 100.0 - c.used_percent)
+        .unwrap_or(100.0);
 
-    reason
+    config.evaluate(five_hour_remaining, weekly_remaining, code_review_remaining)
 }
 
 pub fn cmd_cycle_now(config_dir: &Path, force: bool) -> Result<()> {
@@ -2172,21 +2204,14 @@ where
     let cli = Cli::parse_from(args);
     let config_dir = cli.config_dir.unwrap_or_else(get_config_dir_default);
 
-    tracing_subscriber::fmt()
-        .with_max_level(if cli.verbose {
-            tracing::Level::DEBUG
-        } else {
-            tracing::Level::INFO
-        })
-        .try_init()
-        .ok();
+    cli.log_format.init(cli.verbose);
 
     tracing::debug!("Config directory: {:?}", config_dir);
 
     if !config_dir.exists() {
-        fs::create_dir_all(&config_dir)?;
-        tracing::info!("Created config directory: {:?}", config_dir);
+        tracing::info!("Creating config directory: {:?}", config_dir);
     }
+    paths::ensure_config_layout(&config_dir)?;
 
     match cli.command {
         Commands::Status {
@@ -0,0 +1,27 @@
+//! Log output setup shared by the CLI binary and the Python/Node library.
+
+/// Format of log lines (`--log-format`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum LogFormat {
+    #[default]
+    Text,
+    /// One JSON object per line, for log aggregators.
+    Json,
+}
+
+impl LogFormat {
+    /// Installs the global tracing subscriber at DEBUG when `verbose`, else INFO.
+    /// Does nothing if one is already installed.
+    pub fn init(self, verbose: bool) {
+        let level = if verbose {
+            tracing::Level::DEBUG
+        } else {
+            tracing::Level::INFO
+        };
+        let builder = tracing_subscriber::fmt().with_max_level(level);
+        let _ = match self {
+            LogFormat::Text => builder.try_init(),
+            LogFormat::Json => builder.json().try_init(),
+        };
+    }
+}
@@ -0,0 +1,314 @@
+//! Types persisted in `config.json` and `cycle.json`, shared by the CLI
+//! binary and the Python/Node library so both read and write one schema.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Current `config.json` schema version; files without one are version 0.
+pub const CONFIG_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Config {
+    #[serde(default)]
+    pub version: u32,
+    pub active_account: Option<String>,
+    pub accounts: HashMap<String, AccountInfo>,
+    #[serde(default, skip_serializing_if = "Preferences::is_empty")]
+    pub preferences: Preferences,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            version: CONFIG_VERSION,
+            active_account: None,
+            accounts: HashMap::new(),
+            preferences: Preferences::default(),
+        }
+    }
+}
+
+/// Upgrades a parsed `config.json` to [`CONFIG_VERSION`] in place, returning
+/// whether anything changed. Files from a newer release are rejected rather
+/// than loaded and rewritten without the fields this build doesn't know.
+pub fn migrate_config(value: &mut serde_json::Value) -> Result<bool> {
+    let object = value
+        .as_object_mut()
+        .context("Failed to parse config: expected a JSON object")?;
+    let version = object.get("version").and_then(|v| v.as_u64()).unwrap_or(0);
+    if version > CONFIG_VERSION as u64 {
+        anyhow::bail!(
+            "config.json has schema version {}, but this codex-usage only understands up to {}; please upgrade",
+            version,
+            CONFIG_VERSION
+        );
+    }
+    if version == CONFIG_VERSION as u64 {
+        return Ok(false);
+    }
+
+    // 0 -> 1: early files could omit the account map and per-account
+    // auth_hash; write them out explicitly and stamp the version.
+    object
+        .entry("active_account")
+        .or_insert(serde_json::Value::Null);
+    let accounts = object
+        .entry("accounts")
+        .or_insert_with(|| serde_json::json!({}));
+    if let Some(accounts) = accounts.as_object_mut() {
+        for info in accounts.values_mut() {
+            if let Some(info) = info.as_object_mut() {
+                info.entry("auth_hash").or_insert(serde_json::Value::Null);
+            }
+        }
+    }
+    object.insert("version".to_string(), CONFIG_VERSION.into());
+    Ok(true)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AccountInfo {
+    pub added_at: String,
+    pub last_used: Option<String>,
+    /// SHA-256 of the auth file when the account was added.
+    #[serde(default)]
+    pub auth_hash: Option<String>,
+    /// Alternative names accepted wherever this account is referenced.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    /// Email decoded from the OAuth token when the account was added.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+}
+
+/// Defaults set with `config set`; an explicit flag always wins.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct Preferences {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<StatusFormat>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<ColorChoice>,
+    /// Seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_ttl: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warn_at: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub critical_at: Option<f64>,
+}
+
+impl Preferences {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// When to decorate output with emoji and ANSI escapes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorChoice {
+    Always,
+    Auto,
+    Never,
+}
+
+impl ColorChoice {
+    /// `--color always` wins over `NO_COLOR`; `auto` also requires a TTY.
+    pub fn enabled(self, no_color: bool, is_tty: bool) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => !no_color && is_tty,
+        }
+    }
+}
+
+/// Output format for `status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatusFormat {
+    Text,
+    Json,
+    Yaml,
+    Toml,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CycleConfig {
+    pub enabled: bool,
+    pub thresholds: CycleThresholds,
+    /// `and` cycles when both windows are under their thresholds, `or` when either is.
+    pub mode: String,
+    pub accounts: Vec<String>,
+    pub current_index: usize,
+    pub last_cycle: Option<String>,
+    #[serde(default)]
+    pub strategy: CycleStrategy,
+    /// Whether a recurring `cycle now` job is installed in the OS scheduler.
+    #[serde(default)]
+    pub schedule_enabled: bool,
+    /// Interval of that job, kept after disabling so it can be shown.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule_interval_secs: Option<u64>,
+}
+
+/// Defaults used when `cycle.json` doesn't exist yet. `mode` is `or`, the CLI's
+/// long-standing default; the Python/Node bindings defaulted to `and` before
+/// the two shared this type.
+impl Default for CycleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            thresholds: CycleThresholds::default(),
+            mode: "or".to_string(),
+            accounts: Vec::new(),
+            current_index: 0,
+            last_cycle: None,
+            strategy: CycleStrategy::default(),
+            schedule_enabled: false,
+            schedule_interval_secs: None,
+        }
+    }
+}
+
+impl CycleConfig {
+    /// (label, remaining %, threshold) for every window taking part. The
+    /// code-review window only takes part when it has a threshold.
+    fn windows(
+        &self,
+        five_hour_remaining: f64,
+        weekly_remaining: f64,
+        code_review_remaining: f64,
+    ) -> Vec<(&'static str, f64, f64)> {
+        let mut windows = vec![
+            ("5h", five_hour_remaining, self.thresholds.five_hour),
+            ("weekly", weekly_remaining, self.thresholds.weekly),
+        ];
+        if let Some(threshold) = self.thresholds.code_review {
+            windows.push(("code review", code_review_remaining, threshold));
+        }
+        windows
+    }
+
+    /// Labels of the windows at or under their threshold, as named in
+    /// [`CycleConfig::evaluate`]'s reason.
+    pub fn triggered_windows(
+        &self,
+        five_hour_remaining: f64,
+        weekly_remaining: f64,
+        code_review_remaining: f64,
+    ) -> Vec<&'static str> {
+        self.windows(five_hour_remaining, weekly_remaining, code_review_remaining)
+            .into_iter()
+            .filter(|(_, remaining, threshold)| remaining <= threshold)
+            .map(|(label, _, _)| label)
+            .collect()
+    }
+
+    /// Whether to cycle given each window's remaining %, with a reason naming
+    /// the windows that triggered (or summarising all of them when none did).
+    pub fn evaluate(
+        &self,
+        five_hour_remaining: f64,
+        weekly_remaining: f64,
+        code_review_remaining: f64,
+    ) -> (bool, String) {
+        let windows = self.windows(five_hour_remaining, weekly_remaining, code_review_remaining);
+        let triggered: Vec<String> = windows
+            .iter()
+            .filter(|(_, remaining, threshold)| remaining <= threshold)
+            .map(|(label, remaining, _)| format!("{}: {:.0}% remaining", label, remaining))
+            .collect();
+
+        if self.mode == "and" && triggered.len() == windows.len() {
+            (true, triggered.join(", "))
+        } else if self.mode != "and" && !triggered.is_empty() {
+            (true, triggered[0].clone())
+        } else {
+            let summary: Vec<String> = windows
+                .iter()
+                .map(|(label, remaining, _)| format!("{}: {:.0}%", label, remaining))
+                .collect();
+            (false, summary.join(", "))
+        }
+    }
+}
+
+/// How `cycle now` picks the account to switch to.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum CycleStrategy {
+    /// The next account in the rotation
+    #[default]
+    RoundRobin,
+    /// The account with the most remaining quota
+    LeastUsed,
+    /// The account whose 5h window resets first
+    ResetSoonest,
+}
+
+impl std::fmt::Display for CycleStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            CycleStrategy::RoundRobin => "round-robin",
+            CycleStrategy::LeastUsed => "least-used",
+            CycleStrategy::ResetSoonest => "reset-soonest",
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CycleThresholds {
+    pub five_hour: f64,
+    pub weekly: f64,
+    /// Remaining code-review % that triggers a switch; `None` ignores that window.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_review: Option<f64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_config_stamps_version_and_rejects_newer() {
+        let mut legacy = serde_json::json!({
+            "accounts": {"work": {"added_at": "2024-01-01T00:00:00Z", "last_used": null}}
+        });
+        assert!(migrate_config(&mut legacy).unwrap());
+        assert_eq!(legacy["version"], CONFIG_VERSION);
+        assert!(legacy["accounts"]["work"]["auth_hash"].is_null());
+        let config: Config = serde_json::from_value(legacy.clone()).unwrap();
+        assert!(config.active_account.is_none());
+        assert!(!migrate_config(&mut legacy).unwrap());
+
+        let mut newer = serde_json::json!({"version": CONFIG_VERSION + 1, "accounts": {}});
+        assert!(migrate_config(&mut newer).is_err());
+    }
+
+    #[test]
+    fn test_cycle_evaluate_includes_code_review_only_with_a_threshold() {
+        let mut config = CycleConfig {
+            thresholds: CycleThresholds {
+                five_hour: 10.0,
+                weekly: 10.0,
+                code_review: None,
+            },
+            ..CycleConfig::default()
+        };
+        assert_eq!(config.mode, "or");
+        assert!(!config.evaluate(50.0, 50.0, 0.0).0);
+
+        config.thresholds.code_review = Some(5.0);
+        assert_eq!(
+            config.evaluate(50.0, 50.0, 0.0),
+            (true, "code review: 0% remaining".to_string())
+        );
+        assert_eq!(config.triggered_windows(50.0, 50.0, 0.0), ["code review"]);
+
+        config.mode = "and".to_string();
+        assert!(!config.evaluate(5.0, 5.0, 50.0).0);
+        assert!(config.evaluate(5.0, 5.0, 0.0).0);
+    }
+}
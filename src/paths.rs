@@ -0,0 +1,93 @@
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+const APP_DIR: &str = "codex-usage";
+const LEGACY_DIR: &str = ".codex-usage";
+
+/// Default config directory when neither `--config-dir` nor
+/// `CODEX_USAGE_DIR` is given: `$XDG_CONFIG_HOME/codex-usage` (or the
+/// platform config dir on Linux), unless only the legacy `~/.codex-usage`
+/// exists, in which case that keeps being used.
+pub fn default_config_dir() -> PathBuf {
+    let legacy = dirs::home_dir()
+        .map(|p| p.join(LEGACY_DIR))
+        .unwrap_or_else(|| PathBuf::from(LEGACY_DIR));
+    resolve_config_dir(xdg_config_dir(), legacy)
+}
+
+fn xdg_config_dir() -> Option<PathBuf> {
+    // The XDG spec says relative values are invalid and must be ignored.
+    if let Some(dir) = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+    {
+        return Some(dir.join(APP_DIR));
+    }
+    if cfg!(target_os = "linux") {
+        dirs::config_dir().map(|p| p.join(APP_DIR))
+    } else {
+        None
+    }
+}
+
+fn resolve_config_dir(xdg: Option<PathBuf>, legacy: PathBuf) -> PathBuf {
+    match xdg {
+        Some(xdg) if xdg.exists() || !legacy.exists() => xdg,
+        _ => legacy,
+    }
+}
+
+/// Creates the config dir, its `accounts/` subdir and an empty history
+/// database file, so commands can assume the layout exists instead of each
+/// creating (and reporting failures for) the parts they need.
+pub fn ensure_config_layout(config_dir: &Path) -> Result<()> {
+    let accounts_dir = config_dir.join("accounts");
+    std::fs::create_dir_all(&accounts_dir)
+        .with_context(|| format!("Failed to create config directory {:?}", accounts_dir))?;
+    let db_path = crate::history::get_history_db_path(config_dir);
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&db_path)
+        .with_context(|| format!("Failed to create history database {:?}", db_path))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_config_dir_prefers_xdg_unless_only_legacy_exists() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let xdg = tmp.path().join("xdg/codex-usage");
+        let legacy = tmp.path().join(".codex-usage");
+
+        assert_eq!(resolve_config_dir(Some(xdg.clone()), legacy.clone()), xdg);
+        assert_eq!(resolve_config_dir(None, legacy.clone()), legacy);
+
+        std::fs::create_dir_all(&legacy).expect("create legacy dir");
+        assert_eq!(
+            resolve_config_dir(Some(xdg.clone()), legacy.clone()),
+            legacy
+        );
+
+        std::fs::create_dir_all(&xdg).expect("create xdg dir");
+        assert_eq!(resolve_config_dir(Some(xdg.clone()), legacy), xdg);
+    }
+
+    #[test]
+    fn test_ensure_config_layout_creates_nested_dirs_and_keeps_data() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let config_dir = tmp.path().join("a/b/c");
+
+        ensure_config_layout(&config_dir).expect("create layout");
+        assert!(config_dir.join("accounts").is_dir());
+        let db_path = crate::history::get_history_db_path(&config_dir);
+        assert!(db_path.is_file());
+
+        std::fs::write(&db_path, "data").expect("write db");
+        ensure_config_layout(&config_dir).expect("existing layout");
+        assert_eq!(std::fs::read_to_string(&db_path).expect("read db"), "data");
+    }
+}
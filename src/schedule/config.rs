@@ -1,8 +1,15 @@
 use chrono::NaiveTime;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::time::Duration;
 use thiserror::Error;
 
+/// Prompt sent to Codex when a wakeup needs to start the usage windows.
+pub const DEFAULT_WAKEUP_PROMPT: &str = "hi";
+
+/// Minimum minutes between `wakeup --run --prompt` triggers.
+pub const DEFAULT_PROMPT_COOLDOWN_MINUTES: u64 = 30;
+
 #[derive(Debug, Error)]
 pub enum ScheduleError {
     #[error("At least one time must be specified")]
@@ -21,6 +28,7 @@ pub struct WakeupSchedule {
     pub interval: Option<Duration>,
     pub wake_system: bool,
     pub enabled: bool,
+    /// ISO weekdays the schedule runs on (1 = Monday); all seven means every day.
     pub days: Vec<u8>,
 }
 
@@ -56,7 +64,7 @@ impl Default for WakeupSchedule {
             interval: None,
             wake_system: false,
             enabled: true,
-            days: vec![1, 2, 3, 4, 5],
+            days: (1..=7).collect(),
         }
     }
 }
@@ -84,6 +92,16 @@ impl WakeupSchedule {
         self
     }
 
+    pub fn with_days(mut self, days: Vec<u8>) -> Self {
+        self.days = days;
+        self
+    }
+
+    /// Whether the schedule is limited to some days of the week.
+    pub fn restricts_days(&self) -> bool {
+        !self.days.is_empty() && !(1..=7).all(|d| self.days.contains(&d))
+    }
+
     pub fn with_wake_system(mut self, wake_system: bool) -> Self {
         self.wake_system = wake_system;
         self
@@ -101,15 +119,60 @@ impl WakeupSchedule {
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct WakeupConfig {
     pub schedules: Vec<WakeupSchedule>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_cooldown_minutes: Option<u64>,
+    /// Unix timestamp of the last wakeup prompt per account, for the cooldown.
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        deserialize_with = "deserialize_last_prompt_at"
+    )]
+    pub last_prompt_at: HashMap<String, i64>,
+}
+
+/// Older files stored one timestamp for all accounts; it's dropped rather than
+/// guessed onto an account, so the first prompt after upgrading isn't held back.
+fn deserialize_last_prompt_at<'de, D>(deserializer: D) -> Result<HashMap<String, i64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    match serde_json::Value::deserialize(deserializer)? {
+        value @ serde_json::Value::Object(_) => {
+            serde_json::from_value(value).map_err(serde::de::Error::custom)
+        }
+        _ => Ok(HashMap::new()),
+    }
 }
 
 impl WakeupConfig {
     pub fn new() -> Self {
         Self {
             schedules: Vec::new(),
+            prompt: None,
+            prompt_cooldown_minutes: None,
+            last_prompt_at: HashMap::new(),
         }
     }
 
+    pub fn prompt_cooldown_minutes(&self) -> u64 {
+        self.prompt_cooldown_minutes
+            .unwrap_or(DEFAULT_PROMPT_COOLDOWN_MINUTES)
+    }
+
+    /// Seconds left before another prompt may be sent to `account` at `now`,
+    /// if any.
+    pub fn prompt_cooldown_remaining(&self, account: &str, now: i64) -> Option<i64> {
+        let ready_at =
+            self.last_prompt_at.get(account)? + self.prompt_cooldown_minutes() as i64 * 60;
+        (ready_at > now).then(|| ready_at - now)
+    }
+
+    pub fn prompt(&self) -> &str {
+        self.prompt.as_deref().unwrap_or(DEFAULT_WAKEUP_PROMPT)
+    }
+
     pub fn add_schedule(&mut self, schedule: WakeupSchedule) {
         if let Some(existing) = self.schedules.iter_mut().find(|s| s.name == schedule.name) {
             *existing = schedule;
@@ -3,22 +3,21 @@ pub mod parse;
 pub mod platform;
 
 pub use config::{WakeupConfig, WakeupSchedule};
-pub use parse::{parse_duration, parse_time};
+pub use parse::{format_duration, parse_duration, parse_schedule_interval, parse_time};
 
 #[allow(unused_imports)]
-use parse::{format_duration, format_time};
+use parse::format_time;
 
 use anyhow::{Context, Result};
+use chrono::{Datelike, NaiveDateTime, NaiveTime, Timelike};
+use std::collections::BTreeSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 #[allow(dead_code)]
 pub fn get_wakeup_config_path() -> Result<PathBuf> {
-    let config_dir = dirs::home_dir()
-        .map(|p| p.join(".codex-usage"))
-        .unwrap_or_else(|| PathBuf::from(".codex-usage"));
-    Ok(config_dir.join("wakeup.json"))
+    Ok(crate::paths::default_config_dir().join("wakeup.json"))
 }
 
 pub fn get_wakeup_config_path_from_dir(config_dir: &Path) -> PathBuf {
@@ -75,9 +74,11 @@ pub fn create_schedule(
     interval: Option<Duration>,
     account: Option<String>,
     wake_system: bool,
+    days: Vec<u8>,
 ) -> Result<WakeupSchedule> {
     let schedule = WakeupSchedule::new(name)
         .with_times(times)
+        .with_days(days)
         .with_account(account)
         .with_wake_system(wake_system);
 
@@ -90,3 +91,118 @@ pub fn create_schedule(
     schedule.validate().map_err(|e| anyhow::anyhow!("{}", e))?;
     Ok(schedule)
 }
+
+/// Minutes past midnight at which a schedule fires, expanding `interval` repeats
+/// after each time until the end of the day.
+pub fn trigger_minutes(times: &[NaiveTime], interval: Option<Duration>) -> BTreeSet<u32> {
+    const MINUTES_PER_DAY: u32 = 24 * 60;
+
+    let step = interval
+        .map(|i| (i.as_secs() / 60) as u32)
+        .filter(|&m| m > 0);
+
+    let mut minutes = BTreeSet::new();
+    for time in times {
+        let mut minute = time.hour() * 60 + time.minute();
+        minutes.insert(minute);
+        if let Some(step) = step {
+            minute += step;
+            while minute < MINUTES_PER_DAY {
+                minutes.insert(minute);
+                minute += step;
+            }
+        }
+    }
+    minutes
+}
+
+/// The next `count` times after `after` at which `schedule` fires, in the
+/// same (local) clock its times were given in.
+pub fn next_triggers(
+    schedule: &WakeupSchedule,
+    after: NaiveDateTime,
+    count: usize,
+) -> Vec<NaiveDateTime> {
+    let minutes = trigger_minutes(&schedule.times, schedule.interval);
+    let mut triggers = Vec::new();
+    // A week ahead covers every weekday the schedule can run on.
+    for date in after.date().iter_days().take(8) {
+        let weekday = date.weekday().number_from_monday() as u8;
+        if schedule.restricts_days() && !schedule.days.contains(&weekday) {
+            continue;
+        }
+        for &minute in &minutes {
+            let Some(time) = NaiveTime::from_hms_opt(minute / 60, minute % 60, 0) else {
+                continue;
+            };
+            let at = date.and_time(time);
+            if at > after {
+                triggers.push(at);
+                if triggers.len() == count {
+                    return triggers;
+                }
+            }
+        }
+    }
+    triggers
+}
+
+/// Trigger times shared by two schedules, as produced by [`trigger_minutes`].
+pub fn colliding_times(a: &BTreeSet<u32>, b: &BTreeSet<u32>) -> Vec<NaiveTime> {
+    a.intersection(b)
+        .filter_map(|m| NaiveTime::from_hms_opt(m / 60, m % 60, 0))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn test_trigger_minutes_expands_interval_until_end_of_day() {
+        let minutes = trigger_minutes(&[time(20, 0)], Some(Duration::from_secs(90 * 60)));
+        assert_eq!(
+            minutes.into_iter().collect::<Vec<_>>(),
+            vec![20 * 60, 21 * 60 + 30, 23 * 60]
+        );
+    }
+
+    #[test]
+    fn test_colliding_times() {
+        let wakeup = trigger_minutes(&[time(8, 0), time(14, 0)], None);
+        let cycle = trigger_minutes(&[time(0, 0)], Some(Duration::from_secs(2 * 3600)));
+        assert_eq!(
+            colliding_times(&wakeup, &cycle),
+            vec![time(8, 0), time(14, 0)]
+        );
+
+        let staggered = trigger_minutes(&[time(8, 5)], None);
+        assert!(colliding_times(&staggered, &cycle).is_empty());
+    }
+
+    #[test]
+    fn test_next_triggers_skips_excluded_days() {
+        // 2025-01-03 is a Friday.
+        let after = chrono::NaiveDate::from_ymd_opt(2025, 1, 3)
+            .and_then(|d| d.and_hms_opt(15, 0, 0))
+            .expect("datetime");
+        let schedule = WakeupSchedule::new("default")
+            .with_times(vec![time(8, 0), time(14, 0)])
+            .with_days(vec![1, 2, 3, 4, 5]);
+        let next: Vec<String> = next_triggers(&schedule, after, 3)
+            .iter()
+            .map(|t| t.format("%a %H:%M").to_string())
+            .collect();
+        assert_eq!(next, ["Mon 08:00", "Mon 14:00", "Tue 08:00"]);
+
+        let every_day = WakeupSchedule::new("default").with_times(vec![time(16, 0)]);
+        assert_eq!(
+            next_triggers(&every_day, after, 1),
+            [after.date().and_time(time(16, 0))]
+        );
+    }
+}
@@ -8,6 +8,10 @@ pub enum ParseError {
     InvalidTime(String),
     #[error("Invalid duration format: {0}")]
     InvalidDuration(String),
+    #[error("Duration '{0}' needs a unit (e.g. {0}m or {0}h); bare numbers are ambiguous here")]
+    MissingUnit(String),
+    #[error("Invalid days: {0} (use e.g. all, mon-fri or sat,sun)")]
+    InvalidDays(String),
 }
 
 pub fn parse_time(input: &str) -> Result<NaiveTime, ParseError> {
@@ -115,7 +119,16 @@ pub fn parse_duration(input: &str) -> Result<Duration, ParseError> {
     Err(ParseError::InvalidDuration(input))
 }
 
-#[allow(dead_code)]
+/// Like [`parse_duration`], but for scheduler-facing intervals where a bare
+/// number could mean seconds or minutes, so an explicit unit is required.
+pub fn parse_schedule_interval(input: &str) -> Result<Duration, ParseError> {
+    let trimmed = input.trim();
+    if trimmed.parse::<f64>().is_ok() {
+        return Err(ParseError::MissingUnit(trimmed.to_string()));
+    }
+    parse_duration(trimmed)
+}
+
 pub fn format_duration(duration: &Duration) -> String {
     let total_secs = duration.as_secs();
 
@@ -157,6 +170,51 @@ pub fn format_duration(duration: &Duration) -> String {
     }
 }
 
+const DAY_NAMES: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+
+fn parse_day(input: &str) -> Option<u8> {
+    let input = input.trim();
+    if input.len() < 3 {
+        return None;
+    }
+    DAY_NAMES
+        .iter()
+        .position(|day| input.starts_with(day))
+        .map(|i| i as u8 + 1)
+}
+
+/// Parses a day-of-week list such as `all`, `mon-fri` or `sat,sun` into
+/// sorted ISO weekday numbers (1 = Monday). Ranges may wrap, e.g. `fri-mon`.
+pub fn parse_days(input: &str) -> Result<Vec<u8>, ParseError> {
+    let input = input.trim().to_lowercase();
+    if input == "all" || input == "*" {
+        return Ok((1..=7).collect());
+    }
+
+    let invalid = || ParseError::InvalidDays(input.clone());
+    let mut days = std::collections::BTreeSet::new();
+    for part in input.split(',') {
+        match part.split_once('-') {
+            Some((start, end)) => {
+                let start = parse_day(start).ok_or_else(invalid)?;
+                let end = parse_day(end).ok_or_else(invalid)?;
+                let mut day = start;
+                loop {
+                    days.insert(day);
+                    if day == end {
+                        break;
+                    }
+                    day = day % 7 + 1;
+                }
+            }
+            None => {
+                days.insert(parse_day(part).ok_or_else(invalid)?);
+            }
+        }
+    }
+    Ok(days.into_iter().collect())
+}
+
 pub fn format_time(time: &NaiveTime) -> String {
     time.format("%H:%M").to_string()
 }
@@ -207,6 +265,7 @@ mod tests {
         assert_eq!(parse_duration("5m").unwrap(), Duration::from_secs(300));
         assert_eq!(parse_duration("1h").unwrap(), Duration::from_secs(3600));
         assert_eq!(parse_duration("0.5d").unwrap(), Duration::from_secs(43200));
+        assert_eq!(parse_duration("10").unwrap(), Duration::from_secs(10));
     }
 
     #[test]
@@ -215,4 +274,33 @@ mod tests {
         assert_eq!(format_duration(&Duration::from_secs(3661)), "1h1m");
         assert_eq!(format_duration(&Duration::from_secs(90061)), "1d1h1m");
     }
+
+    #[test]
+    fn test_parse_schedule_interval_requires_unit() {
+        assert_eq!(
+            parse_schedule_interval("30m").unwrap(),
+            Duration::from_secs(1800)
+        );
+        assert!(matches!(
+            parse_schedule_interval("5"),
+            Err(ParseError::MissingUnit(_))
+        ));
+        assert!(matches!(
+            parse_schedule_interval("0.5"),
+            Err(ParseError::MissingUnit(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("all").unwrap(), vec![1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(parse_days("mon-fri").unwrap(), vec![1, 2, 3, 4, 5]);
+        assert_eq!(parse_days("Sat,Sunday").unwrap(), vec![6, 7]);
+        assert_eq!(parse_days("fri-mon").unwrap(), vec![1, 5, 6, 7]);
+        assert!(matches!(
+            parse_days("mon-xyz"),
+            Err(ParseError::InvalidDays(_))
+        ));
+        assert!(parse_days("").is_err());
+    }
 }
@@ -1,10 +1,11 @@
 use crate::schedule::config::WakeupSchedule;
-use crate::schedule::parse::format_time;
+use crate::schedule::parse::{format_duration, format_time};
+use crate::schedule::platform::{wakeup_run_args, IntervalJob};
+use crate::schedule::trigger_minutes;
 use anyhow::{Context, Result};
-use chrono::Timelike;
 use plist::Value;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 const LAUNCH_AGENT_LABEL: &str = "com.codex-usage.wakeup";
@@ -23,7 +24,40 @@ pub fn get_launch_agent_path() -> Result<PathBuf> {
     Ok(launch_agents.join(format!("{}.plist", LAUNCH_AGENT_LABEL)))
 }
 
-pub fn install_schedule(schedule: &WakeupSchedule) -> Result<()> {
+/// `StartCalendarInterval` entries for every trigger of `schedule`: each
+/// `--at` time, then every `--interval` after it until midnight. launchd's
+/// own `StartInterval` can't be anchored to a time of day, so the repeats
+/// are expanded into calendar entries instead, one per weekday when the
+/// schedule skips some days.
+fn calendar_interval_entries(schedule: &WakeupSchedule) -> Vec<String> {
+    let weekdays: Vec<Option<u8>> = if schedule.restricts_days() {
+        schedule.days.iter().map(|&d| Some(d)).collect()
+    } else {
+        vec![None]
+    };
+    let mut entries = Vec::new();
+    for minute in trigger_minutes(&schedule.times, schedule.interval) {
+        for weekday in &weekdays {
+            let weekday = weekday
+                .map(|d| {
+                    format!(
+                        "\n            <key>Weekday</key>\n            <integer>{}</integer>",
+                        d
+                    )
+                })
+                .unwrap_or_default();
+            entries.push(format!(
+                "        <dict>\n            <key>Hour</key>\n            <integer>{}</integer>\n            <key>Minute</key>\n            <integer>{}</integer>{}\n        </dict>",
+                minute / 60,
+                minute % 60,
+                weekday
+            ));
+        }
+    }
+    entries
+}
+
+pub fn install_schedule(schedule: &WakeupSchedule, config_dir: &Path) -> Result<()> {
     let plist_path = get_launch_agent_path()?;
 
     if let Some(parent) = plist_path.parent() {
@@ -32,11 +66,7 @@ pub fn install_schedule(schedule: &WakeupSchedule) -> Result<()> {
 
     let times_str: Vec<String> = schedule.times.iter().map(format_time).collect();
 
-    let mut program_args = vec!["wakeup".to_string(), "--run".to_string()];
-    if let Some(ref account) = schedule.account {
-        program_args.push("--account".to_string());
-        program_args.push(account.clone());
-    }
+    let mut program_args = wakeup_run_args(schedule, config_dir);
     if schedule.wake_system {
         program_args.push("--wake-system".to_string());
     }
@@ -69,16 +99,7 @@ pub fn install_schedule(schedule: &WakeupSchedule) -> Result<()> {
             .map(|s| format!("<string>{}</string>", escape_xml(s)))
             .collect::<Vec<_>>()
             .join("\n        "),
-        schedule
-            .times
-            .iter()
-            .map(|t| format!(
-                "        <dict>\n            <key>Hour</key>\n            <integer>{}</integer>\n            <key>Minute</key>\n            <integer>{}</integer>\n        </dict>",
-                t.hour(),
-                t.minute()
-            ))
-            .collect::<Vec<_>>()
-            .join("\n")
+        calendar_interval_entries(schedule).join("\n")
     );
 
     fs::write(&plist_path, plist_content).context("Failed to write launchd plist")?;
@@ -104,11 +125,19 @@ pub fn install_schedule(schedule: &WakeupSchedule) -> Result<()> {
         anyhow::bail!("Failed to bootstrap launchd agent: {}", stderr);
     }
 
-    println!(
-        "Installed wakeup schedule: {} at {}",
-        schedule.name,
-        times_str.join(", ")
-    );
+    match schedule.interval {
+        Some(interval) => println!(
+            "Installed wakeup schedule: {} at {}, then every {} until midnight",
+            schedule.name,
+            times_str.join(", "),
+            format_duration(&interval)
+        ),
+        None => println!(
+            "Installed wakeup schedule: {} at {}",
+            schedule.name,
+            times_str.join(", ")
+        ),
+    }
 
     if schedule.wake_system {
         install_system_wake(schedule)?;
@@ -206,6 +235,89 @@ pub fn list_schedules() -> Result<Vec<String>> {
     Ok(schedules)
 }
 
+fn interval_job_label(name: &str) -> String {
+    format!("com.codex-usage.{}", name)
+}
+
+fn interval_job_path(name: &str) -> Result<PathBuf> {
+    let home = dirs::home_dir().context("Could not find home directory")?;
+    Ok(home
+        .join("Library/LaunchAgents")
+        .join(format!("{}.plist", interval_job_label(name))))
+}
+
+pub fn install_interval_job(job: &IntervalJob) -> Result<()> {
+    let label = interval_job_label(job.name);
+    let plist_path = interval_job_path(job.name)?;
+    if let Some(parent) = plist_path.parent() {
+        fs::create_dir_all(parent).context("Failed to create LaunchAgents directory")?;
+    }
+
+    let plist_content = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{}</string>
+    <key>ProgramArguments</key>
+    <array>
+        <string>codex-usage</string>
+        {}
+    </array>
+    <key>StartInterval</key>
+    <integer>{}</integer>
+    <key>RunAtLoad</key>
+    <false/>
+</dict>
+</plist>"#,
+        escape_xml(&label),
+        job.args
+            .iter()
+            .map(|s| format!("<string>{}</string>", escape_xml(s)))
+            .collect::<Vec<_>>()
+            .join("\n        "),
+        job.interval.as_secs()
+    );
+    fs::write(&plist_path, plist_content).context("Failed to write launchd plist")?;
+
+    let target = format!("gui/{}", nix::unistd::Uid::current().as_raw());
+    let _ = Command::new("launchctl")
+        .arg("bootout")
+        .arg(&target)
+        .arg(&plist_path)
+        .output();
+    let output = Command::new("launchctl")
+        .arg("bootstrap")
+        .arg(&target)
+        .arg(&plist_path)
+        .output()
+        .context("Failed to bootstrap launchd agent")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("Failed to bootstrap launchd agent: {}", stderr);
+    }
+    Ok(())
+}
+
+pub fn remove_interval_job(name: &str) -> Result<()> {
+    let plist_path = interval_job_path(name)?;
+    if !plist_path.exists() {
+        return Ok(());
+    }
+    let target = format!(
+        "gui/{}/{}",
+        nix::unistd::Uid::current().as_raw(),
+        interval_job_label(name)
+    );
+    let _ = Command::new("launchctl")
+        .arg("bootout")
+        .arg(&target)
+        .output();
+    fs::remove_file(&plist_path).context("Failed to remove launchd plist")?;
+    Ok(())
+}
+
 fn install_system_wake(schedule: &WakeupSchedule) -> Result<()> {
     use nix::unistd::Uid;
 
@@ -287,3 +399,28 @@ fn remove_system_wake() -> Result<()> {
     println!("Removed system wake schedule.");
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveTime;
+    use std::time::Duration;
+
+    #[test]
+    fn test_calendar_entries_repeat_interval_until_midnight() {
+        let schedule = WakeupSchedule {
+            times: vec![NaiveTime::from_hms_opt(20, 0, 0).expect("time")],
+            interval: Some(Duration::from_secs(90 * 60)),
+            name: "default".to_string(),
+            account: None,
+            wake_system: false,
+            enabled: true,
+            days: Vec::new(),
+        };
+        let entries = calendar_interval_entries(&schedule);
+        assert_eq!(entries.len(), 3);
+        assert!(entries[1].contains("<integer>21</integer>"));
+        assert!(entries[1].contains("<integer>30</integer>"));
+        assert!(entries[2].contains("<integer>23</integer>"));
+    }
+}
@@ -1,33 +1,158 @@
 use crate::schedule::config::WakeupSchedule;
 use anyhow::Result;
+use std::path::Path;
+use std::time::Duration;
 
 #[cfg(target_os = "macos")]
 mod macos;
 
+#[cfg(target_os = "linux")]
+mod systemd;
+
 #[cfg(target_os = "linux")]
 mod unix;
 
 #[cfg(target_os = "windows")]
 mod windows;
 
-pub fn install(schedule: &WakeupSchedule) -> Result<()> {
+/// Scheduler backend used on Linux.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Scheduler {
+    Cron,
+    Systemd,
+}
+
+/// Whether `program` can be spawned at all; `args` should be harmless.
+#[allow(dead_code)]
+fn program_exists(program: &str, args: &[&str]) -> bool {
+    std::process::Command::new(program)
+        .args(args)
+        .stdin(std::process::Stdio::null())
+        .output()
+        .is_ok()
+}
+
+#[cfg(target_os = "linux")]
+fn resolve_scheduler(scheduler: Option<Scheduler>) -> Scheduler {
+    scheduler.unwrap_or_else(|| {
+        if crate::schedule::platform::systemd::user_manager_available() {
+            Scheduler::Systemd
+        } else {
+            Scheduler::Cron
+        }
+    })
+}
+
+/// Checks that the scheduler `install` would use is available, returning a
+/// short description of it.
+pub fn check(scheduler: Option<Scheduler>) -> Result<&'static str> {
+    #[cfg(target_os = "macos")]
+    {
+        let _ = scheduler;
+        if !program_exists("launchctl", &["help"]) {
+            anyhow::bail!("launchctl not found");
+        }
+        Ok("launchd (launchctl)")
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        match resolve_scheduler(scheduler) {
+            Scheduler::Systemd => {
+                if !crate::schedule::platform::systemd::user_manager_available() {
+                    anyhow::bail!("systemctl --user is not available");
+                }
+                Ok("systemd user timer")
+            }
+            Scheduler::Cron => {
+                if !program_exists("crontab", &["-l"]) {
+                    anyhow::bail!("crontab not found");
+                }
+                Ok("crontab")
+            }
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let _ = scheduler;
+        if !program_exists("schtasks", &["/?"]) {
+            anyhow::bail!("schtasks not found");
+        }
+        Ok("Task Scheduler (schtasks)")
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+    {
+        let _ = scheduler;
+        anyhow::bail!("Unsupported operating system")
+    }
+}
+
+/// Arguments for the scheduled `wakeup --run`. `config_dir` should be
+/// absolute, since jobs don't start in the user's shell or home directory.
+#[allow(dead_code)]
+fn wakeup_run_args(schedule: &WakeupSchedule, config_dir: &Path) -> Vec<String> {
+    let mut args = vec![
+        "--config-dir".to_string(),
+        config_dir.to_string_lossy().to_string(),
+        "wakeup".to_string(),
+        "--run".to_string(),
+    ];
+    if let Some(ref account) = schedule.account {
+        args.push("--account".to_string());
+        args.push(account.clone());
+    }
+    args
+}
+
+/// Installs `schedule` with the OS scheduler. `scheduler` only applies on
+/// Linux, where it defaults to a systemd user timer when `systemctl --user`
+/// works and to crontab otherwise.
+pub fn install(
+    schedule: &WakeupSchedule,
+    config_dir: &Path,
+    scheduler: Option<Scheduler>,
+) -> Result<()> {
     #[cfg(target_os = "macos")]
     {
-        crate::schedule::platform::macos::install_schedule(schedule)
+        if scheduler.is_some() {
+            eprintln!("Warning: --scheduler only applies on Linux; using launchd.");
+        }
+        crate::schedule::platform::macos::install_schedule(schedule, config_dir)
     }
 
     #[cfg(target_os = "linux")]
     {
-        crate::schedule::platform::unix::install_schedule(schedule)
+        use crate::schedule::platform::{systemd, unix};
+
+        // Drop the other backend's entries so a switch doesn't fire twice.
+        match resolve_scheduler(scheduler) {
+            Scheduler::Systemd => {
+                if !systemd::user_manager_available() {
+                    anyhow::bail!("systemctl --user is not available; use --scheduler cron");
+                }
+                unix::remove_cron_entries()?;
+                systemd::install_schedule(schedule, config_dir)
+            }
+            Scheduler::Cron => {
+                systemd::remove_schedule()?;
+                unix::install_schedule(schedule, config_dir)
+            }
+        }
     }
 
     #[cfg(target_os = "windows")]
     {
-        crate::schedule::platform::windows::install_schedule(schedule)
+        if scheduler.is_some() {
+            eprintln!("Warning: --scheduler only applies on Linux; using Task Scheduler.");
+        }
+        crate::schedule::platform::windows::install_schedule(schedule, config_dir)
     }
 
     #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
     {
+        let _ = (schedule, config_dir, scheduler);
         anyhow::bail!("Unsupported operating system")
     }
 }
@@ -40,6 +165,11 @@ pub fn remove() -> Result<()> {
 
     #[cfg(target_os = "linux")]
     {
+        if crate::schedule::platform::systemd::remove_schedule()? {
+            crate::schedule::platform::unix::remove_cron_entries()?;
+            println!("Removed wakeup schedule.");
+            return Ok(());
+        }
         crate::schedule::platform::unix::remove_schedule()
     }
 
@@ -62,7 +192,9 @@ pub fn list() -> Result<Vec<String>> {
 
     #[cfg(target_os = "linux")]
     {
-        crate::schedule::platform::unix::list_schedules()
+        let mut schedules = crate::schedule::platform::systemd::list_schedules()?;
+        schedules.extend(crate::schedule::platform::unix::list_schedules()?);
+        Ok(schedules)
     }
 
     #[cfg(target_os = "windows")]
@@ -75,3 +207,89 @@ pub fn list() -> Result<Vec<String>> {
         Ok(Vec::new())
     }
 }
+
+/// A recurring job that runs `codex-usage <args>` every `interval`,
+/// independent of the wakeup schedule.
+pub struct IntervalJob<'a> {
+    /// Short identifier used in task names, e.g. "cycle".
+    pub name: &'a str,
+    pub args: &'a [String],
+    pub interval: Duration,
+}
+
+/// Whether interval jobs fire at fixed times of day (multiples of the
+/// interval past midnight). cron's step fields are; launchd's `StartInterval`
+/// and Task Scheduler repetitions count from whenever the job was installed.
+pub fn interval_jobs_anchored() -> bool {
+    cfg!(target_os = "linux")
+}
+
+pub fn install_interval(job: &IntervalJob) -> Result<()> {
+    #[cfg(target_os = "macos")]
+    {
+        crate::schedule::platform::macos::install_interval_job(job)
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        crate::schedule::platform::unix::install_interval_job(job)
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        crate::schedule::platform::windows::install_interval_job(job)
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+    {
+        let _ = job;
+        anyhow::bail!("Unsupported operating system")
+    }
+}
+
+pub fn remove_interval(name: &str) -> Result<()> {
+    #[cfg(target_os = "macos")]
+    {
+        crate::schedule::platform::macos::remove_interval_job(name)
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        crate::schedule::platform::unix::remove_interval_job(name)
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        crate::schedule::platform::windows::remove_interval_job(name)
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+    {
+        let _ = name;
+        anyhow::bail!("Unsupported operating system")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wakeup_run_args_pin_config_dir() {
+        let schedule = WakeupSchedule::new("default");
+        let dir = Path::new("/home/me/.codex-usage");
+        assert_eq!(
+            wakeup_run_args(&schedule, dir),
+            ["--config-dir", "/home/me/.codex-usage", "wakeup", "--run"]
+        );
+
+        let schedule = WakeupSchedule {
+            account: Some("work".to_string()),
+            ..schedule
+        };
+        assert_eq!(
+            wakeup_run_args(&schedule, dir)[2..],
+            ["wakeup", "--run", "--account", "work"]
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use crate::schedule::config::WakeupSchedule;
+use crate::schedule::parse::format_time;
+use crate::schedule::platform::wakeup_run_args;
+use crate::schedule::trigger_minutes;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const UNIT_NAME: &str = "codex-usage-wakeup";
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+fn unit_dir() -> Result<PathBuf> {
+    let config = dirs::config_dir().context("Could not find config directory")?;
+    Ok(config.join("systemd/user"))
+}
+
+fn systemctl(args: &[&str]) -> Result<std::process::Output> {
+    Command::new("systemctl")
+        .arg("--user")
+        .args(args)
+        .output()
+        .context("Failed to run systemctl --user")
+}
+
+/// Whether a systemd user manager is reachable, i.e. timers can be used
+/// instead of crontab.
+pub fn user_manager_available() -> bool {
+    systemctl(&["show-environment"])
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+/// Quotes an `ExecStart=` word when it contains whitespace or quotes.
+fn exec_word(word: &str) -> String {
+    if word.contains(|c: char| c.is_whitespace() || c == '"' || c == '\\') {
+        format!("\"{}\"", word.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        word.to_string()
+    }
+}
+
+fn service_unit(program: &str, args: &[String]) -> String {
+    let command: Vec<String> = std::iter::once(program)
+        .chain(args.iter().map(String::as_str))
+        .map(exec_word)
+        .collect();
+    format!(
+        "[Unit]\nDescription=codex-usage wakeup\n\n[Service]\nType=oneshot\nExecStart={}\n",
+        command.join(" ")
+    )
+}
+
+/// One `OnCalendar=` line per trigger, on the schedule's weekdays (1 = Monday).
+fn timer_unit(schedule: &WakeupSchedule) -> String {
+    let days = if schedule.restricts_days() {
+        let days: Vec<&str> = schedule
+            .days
+            .iter()
+            .filter_map(|&d| WEEKDAYS.get((d as usize).checked_sub(1)?).copied())
+            .collect();
+        format!("{} ", days.join(","))
+    } else {
+        String::new()
+    };
+
+    let mut unit = String::from("[Unit]\nDescription=codex-usage wakeup schedule\n\n[Timer]\n");
+    for minute in trigger_minutes(&schedule.times, schedule.interval) {
+        unit.push_str(&format!(
+            "OnCalendar={}*-*-* {:02}:{:02}:00\n",
+            days,
+            minute / 60,
+            minute % 60
+        ));
+    }
+    unit.push_str("Persistent=false\n\n[Install]\nWantedBy=timers.target\n");
+    unit
+}
+
+pub fn install_schedule(schedule: &WakeupSchedule, config_dir: &Path) -> Result<()> {
+    let dir = unit_dir()?;
+    fs::create_dir_all(&dir).context("Failed to create systemd user unit directory")?;
+
+    let args = wakeup_run_args(schedule, config_dir);
+    // Units don't search $PATH, so point at the running binary.
+    let program = std::env::current_exe().context("Failed to locate codex-usage binary")?;
+
+    fs::write(
+        dir.join(format!("{}.service", UNIT_NAME)),
+        service_unit(&program.to_string_lossy(), &args),
+    )
+    .context("Failed to write systemd service unit")?;
+    fs::write(
+        dir.join(format!("{}.timer", UNIT_NAME)),
+        timer_unit(schedule),
+    )
+    .context("Failed to write systemd timer unit")?;
+
+    let _ = systemctl(&["daemon-reload"]);
+    let timer = format!("{}.timer", UNIT_NAME);
+    let output = systemctl(&["enable", "--now", &timer])?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("Failed to enable systemd timer: {}", stderr);
+    }
+    // Restart so an already-enabled timer picks up the new calendar.
+    let _ = systemctl(&["restart", &timer]);
+
+    let times_str: Vec<String> = schedule.times.iter().map(format_time).collect();
+    println!(
+        "Installed wakeup schedule: {} at {} (systemd timer {})",
+        schedule.name,
+        times_str.join(", "),
+        timer
+    );
+    Ok(())
+}
+
+/// Disables and deletes the timer and service; returns whether they existed.
+pub fn remove_schedule() -> Result<bool> {
+    let dir = unit_dir()?;
+    let timer_path = dir.join(format!("{}.timer", UNIT_NAME));
+    let service_path = dir.join(format!("{}.service", UNIT_NAME));
+    if !timer_path.exists() && !service_path.exists() {
+        return Ok(false);
+    }
+
+    match systemctl(&["disable", "--now", &format!("{}.timer", UNIT_NAME)]) {
+        Ok(output) if output.status.success() => {}
+        Ok(output) => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            eprintln!("Warning: Failed to disable systemd timer: {}", stderr);
+        }
+        Err(e) => eprintln!("Warning: {}", e),
+    }
+    for path in [&timer_path, &service_path] {
+        if path.exists() {
+            fs::remove_file(path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+    }
+    let _ = systemctl(&["daemon-reload"]);
+    Ok(true)
+}
+
+pub fn list_schedules() -> Result<Vec<String>> {
+    let timer_path = unit_dir()?.join(format!("{}.timer", UNIT_NAME));
+    if !timer_path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&timer_path).context("Failed to read systemd timer")?;
+    Ok(content
+        .lines()
+        .filter_map(|line| line.strip_prefix("OnCalendar="))
+        .map(|calendar| format!("{} (systemd)", calendar))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveTime;
+    use std::time::Duration;
+
+    #[test]
+    fn test_timer_unit_expands_interval_on_weekdays() {
+        let schedule = WakeupSchedule::new("default")
+            .with_times(vec![NaiveTime::from_hms_opt(21, 0, 0).expect("time")])
+            .with_interval(Duration::from_secs(90 * 60));
+        assert!(timer_unit(&schedule).contains("OnCalendar=*-*-* 21:00:00\n"));
+
+        let unit = timer_unit(&schedule.with_days(vec![1, 2, 3, 4, 5]));
+        assert!(unit.contains("OnCalendar=Mon,Tue,Wed,Thu,Fri *-*-* 21:00:00\n"));
+        assert!(unit.contains("OnCalendar=Mon,Tue,Wed,Thu,Fri *-*-* 22:30:00\n"));
+        assert_eq!(unit.matches("OnCalendar=").count(), 2);
+    }
+
+    #[test]
+    fn test_service_unit_quotes_arguments() {
+        let args = vec!["--account".to_string(), "my work".to_string()];
+        assert!(service_unit("/usr/bin/codex-usage", &args)
+            .contains("ExecStart=/usr/bin/codex-usage --account \"my work\"\n"));
+    }
+}
@@ -1,35 +1,37 @@
 use crate::schedule::config::WakeupSchedule;
-use crate::schedule::parse::format_time;
+use crate::schedule::parse::{format_duration, format_time};
+use crate::schedule::platform::{wakeup_run_args, IntervalJob};
+use crate::schedule::trigger_minutes;
 use anyhow::{Context, Result};
+use std::path::Path;
 use std::process::Command;
+use std::time::Duration;
 
 const CRON_TASK_NAME: &str = "codex-usage-wakeup";
 
-pub fn install_schedule(schedule: &WakeupSchedule) -> Result<()> {
-    let times_str: Vec<String> = schedule.times.iter().map(format_time).collect();
-
-    let mut args = vec!["wakeup".to_string(), "--run".to_string()];
-    if let Some(ref account) = schedule.account {
-        args.push("--account".to_string());
-        args.push(account.clone());
-    }
-
-    let mut cron_entries = Vec::new();
-    for time_str in &times_str {
-        let parts: Vec<&str> = time_str.split(':').collect();
-        if parts.len() >= 2 {
-            let minute = parts[1];
-            let hour = parts[0];
-            let entry = format!(
-                "{} {} * * 1-5 codex-usage {} # {}",
-                minute,
-                hour,
-                args.join(" "),
+/// One crontab line per time the schedule fires in a day, so `--interval`
+/// repeats after each `--at` time just as with the systemd timer.
+fn cron_entries(schedule: &WakeupSchedule, config_dir: &Path) -> Vec<String> {
+    let args = cron_command_args(&wakeup_run_args(schedule, config_dir));
+    let days = cron_days(schedule);
+    trigger_minutes(&schedule.times, schedule.interval)
+        .into_iter()
+        .map(|minute| {
+            format!(
+                "{} {} * * {} codex-usage {} # {}",
+                minute % 60,
+                minute / 60,
+                days,
+                args,
                 CRON_TASK_NAME
-            );
-            cron_entries.push(entry);
-        }
-    }
+            )
+        })
+        .collect()
+}
+
+pub fn install_schedule(schedule: &WakeupSchedule, config_dir: &Path) -> Result<()> {
+    let times_str: Vec<String> = schedule.times.iter().map(format_time).collect();
+    let cron_entries = cron_entries(schedule, config_dir);
 
     let existing_crontab = get_current_crontab().unwrap_or_default();
     let filtered: Vec<String> = existing_crontab
@@ -51,11 +53,19 @@ pub fn install_schedule(schedule: &WakeupSchedule) -> Result<()> {
 
     set_crontab(&new_crontab)?;
 
-    println!(
-        "Installed wakeup schedule: {} at {}",
-        schedule.name,
-        times_str.join(", ")
-    );
+    match schedule.interval {
+        Some(interval) => println!(
+            "Installed wakeup schedule: {} at {}, then every {}",
+            schedule.name,
+            times_str.join(", "),
+            format_duration(&interval)
+        ),
+        None => println!(
+            "Installed wakeup schedule: {} at {}",
+            schedule.name,
+            times_str.join(", ")
+        ),
+    }
     Ok(())
 }
 
@@ -91,6 +101,16 @@ pub fn remove_schedule() -> Result<()> {
     Ok(())
 }
 
+/// Drops wakeup entries from the crontab without reporting, e.g. when
+/// moving the schedule to a systemd timer.
+pub fn remove_cron_entries() -> Result<()> {
+    let crontab = get_current_crontab().unwrap_or_default();
+    if crontab.lines().any(|line| line.contains(CRON_TASK_NAME)) {
+        replace_tagged_entries(CRON_TASK_NAME, &[])?;
+    }
+    Ok(())
+}
+
 pub fn list_schedules() -> Result<Vec<String>> {
     let crontab = get_current_crontab().unwrap_or_default();
     let schedules: Vec<String> = crontab
@@ -102,6 +122,98 @@ pub fn list_schedules() -> Result<Vec<String>> {
     Ok(schedules)
 }
 
+/// Cron day-of-week field for the schedule's days (cron counts Sunday as 0).
+fn cron_days(schedule: &WakeupSchedule) -> String {
+    if !schedule.restricts_days() {
+        return "*".to_string();
+    }
+    schedule
+        .days
+        .iter()
+        .map(|&d| (d % 7).to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Joins `args` into a crontab command line: each argument is quoted for the
+/// shell cron runs it with, and `%` (a newline to cron) is escaped.
+fn cron_command_args(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| {
+            let safe = !arg.is_empty()
+                && arg
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "-_./:=@,+".contains(c));
+            let quoted = if safe {
+                arg.clone()
+            } else {
+                format!("'{}'", arg.replace('\'', "'\\''"))
+            };
+            quoted.replace('%', "\\%")
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn interval_job_tag(name: &str) -> String {
+    format!("codex-usage-{}", name)
+}
+
+/// Minute and hour fields for an interval cron can express exactly: a
+/// divisor of an hour in minutes, or of a day in whole hours.
+fn cron_interval_fields(interval: Duration) -> Result<String> {
+    let minutes = interval.as_secs() / 60;
+    if interval.as_secs() % 60 == 0 && minutes > 0 {
+        if minutes < 60 && 60 % minutes == 0 {
+            return Ok(format!("*/{} *", minutes));
+        }
+        let hours = minutes / 60;
+        if minutes % 60 == 0 && hours <= 24 && 24 % hours == 0 {
+            return Ok(if hours == 24 {
+                "0 0".to_string()
+            } else {
+                format!("0 */{}", hours)
+            });
+        }
+    }
+    anyhow::bail!(
+        "cron can't repeat every {}; use a divisor of 60 minutes (e.g. 15m, 30m) or of 24 hours (e.g. 1h, 2h, 6h)",
+        format_duration(&interval)
+    )
+}
+
+/// Replaces crontab lines tagged with `tag` by `entries`.
+fn replace_tagged_entries(tag: &str, entries: &[String]) -> Result<()> {
+    let existing_crontab = get_current_crontab().unwrap_or_default();
+    let mut lines: Vec<String> = existing_crontab
+        .lines()
+        .filter(|line| !line.ends_with(&format!("# {}", tag)))
+        .map(|s| s.to_string())
+        .collect();
+    lines.extend(entries.iter().cloned());
+
+    if lines.is_empty() {
+        let _ = Command::new("crontab").arg("-r").output();
+        return Ok(());
+    }
+    set_crontab(&format!("{}\n", lines.join("\n")))
+}
+
+pub fn install_interval_job(job: &IntervalJob) -> Result<()> {
+    let tag = interval_job_tag(job.name);
+    let entry = format!(
+        "{} * * * codex-usage {} # {}",
+        cron_interval_fields(job.interval)?,
+        cron_command_args(job.args),
+        tag
+    );
+    replace_tagged_entries(&tag, &[entry])
+}
+
+pub fn remove_interval_job(name: &str) -> Result<()> {
+    replace_tagged_entries(&interval_job_tag(name), &[])
+}
+
 fn get_current_crontab() -> Result<String> {
     let output = Command::new("crontab")
         .arg("-l")
@@ -140,3 +252,68 @@ fn set_crontab(content: &str) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cron_entries_expand_interval() {
+        let at = |h, m| chrono::NaiveTime::from_hms_opt(h, m, 0).unwrap();
+        let schedule = WakeupSchedule {
+            times: vec![at(20, 0)],
+            interval: Some(Duration::from_secs(90 * 60)),
+            ..WakeupSchedule::new("default")
+        };
+        let entries = cron_entries(&schedule, Path::new("/cfg"));
+        let fields: Vec<String> = entries
+            .iter()
+            .map(|e| e.split_whitespace().take(2).collect::<Vec<_>>().join(" "))
+            .collect();
+        assert_eq!(fields, ["0 20", "30 21", "0 23"]);
+        assert!(
+            entries[0].ends_with("codex-usage --config-dir /cfg wakeup --run # codex-usage-wakeup")
+        );
+    }
+
+    #[test]
+    fn test_cron_interval_fields() {
+        let minutes = |m: u64| Duration::from_secs(m * 60);
+        assert_eq!(cron_interval_fields(minutes(15)).unwrap(), "*/15 *");
+        assert_eq!(cron_interval_fields(minutes(120)).unwrap(), "0 */2");
+        assert_eq!(cron_interval_fields(minutes(24 * 60)).unwrap(), "0 0");
+        assert!(cron_interval_fields(minutes(45)).is_err());
+        assert!(cron_interval_fields(minutes(90)).is_err());
+        assert!(cron_interval_fields(Duration::from_secs(30)).is_err());
+    }
+
+    #[test]
+    fn test_cron_command_args() {
+        let args = |a: &[&str]| a.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            cron_command_args(&args(&["--config-dir", "/home/me/.codex-usage", "cycle"])),
+            "--config-dir /home/me/.codex-usage cycle"
+        );
+        assert_eq!(
+            cron_command_args(&args(&[
+                "--config-dir",
+                "/home/my dir/it's",
+                "--account",
+                ""
+            ])),
+            "--config-dir '/home/my dir/it'\\''s' --account ''"
+        );
+        assert_eq!(cron_command_args(&args(&["50%"])), "'50\\%'");
+    }
+
+    #[test]
+    fn test_cron_days() {
+        let schedule = WakeupSchedule::new("default");
+        assert_eq!(cron_days(&schedule), "*");
+        assert_eq!(
+            cron_days(&schedule.clone().with_days(vec![1, 2, 3, 4, 5])),
+            "1,2,3,4,5"
+        );
+        assert_eq!(cron_days(&schedule.with_days(vec![6, 7])), "6,0");
+    }
+}
@@ -1,18 +1,16 @@
 use crate::schedule::config::WakeupSchedule;
 use crate::schedule::parse::format_time;
+use crate::schedule::platform::{wakeup_run_args, IntervalJob};
 use anyhow::{Context, Result};
+use std::path::Path;
 use std::process::Command;
 
 const TASK_NAME: &str = "CodexUsageWakeup";
 
-pub fn install_schedule(schedule: &WakeupSchedule) -> Result<()> {
+pub fn install_schedule(schedule: &WakeupSchedule, config_dir: &Path) -> Result<()> {
     let times_str: Vec<String> = schedule.times.iter().map(format_time).collect();
 
-    let mut args = vec!["wakeup".to_string(), "--run".to_string()];
-    if let Some(ref account) = schedule.account {
-        args.push("--account".to_string());
-        args.push(account.clone());
-    }
+    let args = wakeup_run_args(schedule, config_dir);
 
     let exe_path = std::env::current_exe()
         .context("Failed to get current executable path")?
@@ -30,8 +28,11 @@ pub fn install_schedule(schedule: &WakeupSchedule) -> Result<()> {
         cmd.arg(&task_name);
         cmd.arg("/tr");
         cmd.arg(format!("\"{}\" {}", exe_path, quoted_args.join(" ")));
-        cmd.arg("/sc");
-        cmd.arg("daily");
+        if schedule.restricts_days() {
+            cmd.args(["/sc", "weekly", "/d", &schtasks_days(&schedule.days)]);
+        } else {
+            cmd.args(["/sc", "daily"]);
+        }
         cmd.arg("/st");
         cmd.arg(time_str);
         cmd.arg("/f");
@@ -58,6 +59,15 @@ pub fn install_schedule(schedule: &WakeupSchedule) -> Result<()> {
     Ok(())
 }
 
+/// `schtasks /d` list for ISO weekdays (1 = Monday).
+fn schtasks_days(days: &[u8]) -> String {
+    const NAMES: [&str; 7] = ["MON", "TUE", "WED", "THU", "FRI", "SAT", "SUN"];
+    days.iter()
+        .filter_map(|&d| NAMES.get((d as usize).checked_sub(1)?).copied())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
 pub fn remove_schedule() -> Result<()> {
     let output = Command::new("schtasks")
         .arg("/query")
@@ -131,6 +141,58 @@ pub fn list_schedules() -> Result<Vec<String>> {
     Ok(schedules)
 }
 
+fn interval_task_name(name: &str) -> String {
+    let mut chars = name.chars();
+    let name: String = chars
+        .next()
+        .map(|c| c.to_ascii_uppercase())
+        .into_iter()
+        .chain(chars)
+        .collect();
+    format!("CodexUsage{}", name)
+}
+
+pub fn install_interval_job(job: &IntervalJob) -> Result<()> {
+    let minutes = job.interval.as_secs() / 60;
+    if job.interval.as_secs() % 60 != 0 || !(1..=1439).contains(&minutes) {
+        anyhow::bail!("Task Scheduler intervals must be whole minutes between 1m and 23h59m");
+    }
+    let exe_path = std::env::current_exe()
+        .context("Failed to get current executable path")?
+        .to_string_lossy()
+        .to_string();
+    let quoted_args: Vec<String> = job.args.iter().map(|a| format!("\"{}\"", a)).collect();
+
+    let output = Command::new("schtasks")
+        .arg("/create")
+        .arg("/tn")
+        .arg(interval_task_name(job.name))
+        .arg("/tr")
+        .arg(format!("\"{}\" {}", exe_path, quoted_args.join(" ")))
+        .arg("/sc")
+        .arg("minute")
+        .arg("/mo")
+        .arg(minutes.to_string())
+        .arg("/f")
+        .output()
+        .context("Failed to create scheduled task")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("Failed to create scheduled task: {}", stderr);
+    }
+    Ok(())
+}
+
+pub fn remove_interval_job(name: &str) -> Result<()> {
+    let _ = Command::new("schtasks")
+        .arg("/delete")
+        .arg("/tn")
+        .arg(interval_task_name(name))
+        .arg("/f")
+        .output();
+    Ok(())
+}
+
 /// Enables system wake from sleep on Windows.
 ///
 /// Note: Windows does not support automated wake-from-sleep scheduling via CLI.